@@ -0,0 +1,128 @@
+//! Compares writing and reading rows through the SQL planner (`execute_stmt`)
+//! with `insert_rows` / `scan_rows`, which go straight to the storage.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use gluesql_core::ast_builder::{self, Build};
+use gluesql_core::prelude::{Glue, Payload};
+use gluesql_derive::{
+    insert_rows, scan_rows, FromGlueSqlRow, ReflectGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+const ROWS: u64 = 1_000;
+
+#[derive(Debug, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow)]
+struct User {
+    #[gluesql(primary_key)]
+    id: u64,
+    name: String,
+    score: Option<i64>,
+    created_at: chrono::NaiveDateTime,
+}
+
+fn users() -> Vec<User> {
+    let created_at = chrono::DateTime::from_timestamp(1_700_000_000, 0)
+        .unwrap()
+        .naive_utc();
+    (0..ROWS)
+        .map(|id| User {
+            id,
+            name: format!("user{}", id),
+            score: (id % 2 == 0).then_some(id as i64),
+            created_at,
+        })
+        .collect()
+}
+
+async fn empty_glue() -> Glue<SharedMemoryStorage> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(User::get_ddl("user")).await.unwrap();
+    glue
+}
+
+/// A single multi-row `INSERT`, the fair baseline for `insert_rows`
+async fn insert_stmt(glue: &mut Glue<SharedMemoryStorage>, users: &[User]) {
+    let insert = ast_builder::table("user")
+        .insert()
+        .columns(User::columns())
+        .values(users.iter().map(ToGlueSqlRow::to_gluesql_row).collect())
+        .build()
+        .unwrap();
+    glue.execute_stmt(&insert).await.unwrap();
+}
+
+/// One `INSERT` per row, mostly measuring the per-statement overhead
+async fn insert_stmt_per_row(glue: &mut Glue<SharedMemoryStorage>, users: &[User]) {
+    for user in users {
+        let insert = ast_builder::table("user")
+            .insert()
+            .columns(User::columns())
+            .values(vec![user.to_gluesql_row()])
+            .build()
+            .unwrap();
+        glue.execute_stmt(&insert).await.unwrap();
+    }
+}
+
+/// Both paths start from an empty table, created inside the measured routine
+fn bench_insert(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let users = users();
+    let mut group = c.benchmark_group("insert");
+    group.bench_function("execute_stmt", |b| {
+        b.to_async(&runtime).iter(|| async {
+            let mut glue = empty_glue().await;
+            insert_stmt(&mut glue, &users).await;
+        })
+    });
+    group.bench_function("execute_stmt_per_row", |b| {
+        b.to_async(&runtime).iter(|| async {
+            let mut glue = empty_glue().await;
+            insert_stmt_per_row(&mut glue, &users).await;
+        })
+    });
+    group.bench_function("insert_rows", |b| {
+        b.to_async(&runtime).iter(|| async {
+            let mut glue = empty_glue().await;
+            insert_rows(&mut glue.storage, "user", &users)
+                .await
+                .unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let glue = runtime.block_on(async {
+        let mut glue = empty_glue().await;
+        insert_rows(&mut glue.storage, "user", &users())
+            .await
+            .unwrap();
+        glue
+    });
+    let select = ast_builder::table("user")
+        .select()
+        .project(User::columns())
+        .build()
+        .unwrap();
+    let mut group = c.benchmark_group("scan");
+    group.bench_function("execute_stmt", |b| {
+        b.to_async(&runtime).iter(|| async {
+            // the storage is shared, so this is only a cheap handle
+            let mut glue = Glue::new(glue.storage.clone());
+            match glue.execute_stmt(&select).await.unwrap() {
+                Payload::Select { labels, rows } => User::from_gluesql_rows(&labels, rows).unwrap(),
+                _ => unreachable!(),
+            }
+        })
+    });
+    group.bench_function("scan_rows", |b| {
+        b.to_async(&runtime)
+            .iter(|| async { scan_rows::<_, User>(&glue.storage, "user").await.unwrap() })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_scan);
+criterion_main!(benches);
@@ -0,0 +1,190 @@
+use proc_macro::TokenStream;
+
+use darling::ast::Data;
+use darling::{Error, FromDeriveInput, FromMeta, FromVariant};
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::rename::RenameRule;
+
+/// Fallible entry point for generating the value traits of a fieldless enum
+pub fn try_derive_enum(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let enum_derive = DeriveGluesqlEnum::from_derive_input(input)?;
+    Ok(enum_derive.generate_enum()?)
+}
+
+/// How the variants of an enum are stored in a column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumRepr {
+    /// The (possibly renamed) variant name, as `TEXT`
+    #[default]
+    Text,
+    /// The variant discriminant, as `INT`
+    Int,
+}
+
+impl FromMeta for EnumRepr {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "text" => Ok(EnumRepr::Text),
+            "int" => Ok(EnumRepr::Int),
+            _ => Err(Error::unknown_value(value)),
+        }
+    }
+}
+
+/// Main struct for deriving `ToGlueSql`, `ToGlueSqlValue`, `FromGlueSql` and `ReflectGlueSql` for an enum.
+#[derive(Debug, FromDeriveInput)]
+#[darling(
+    attributes(gluesql),
+    forward_attrs(allow, doc, cfg),
+    supports(enum_unit)
+)]
+pub struct DeriveGluesqlEnum {
+    pub ident: syn::Ident,
+    pub generics: syn::Generics,
+    pub data: Data<GluesqlVariant, ()>,
+    /// Case conversion applied to every variant name that is not explicitly renamed.
+    #[darling(default)]
+    pub rename_all: Option<RenameRule>,
+    /// Whether to store the variant name or its discriminant.
+    #[darling(default)]
+    pub repr: EnumRepr,
+}
+
+/// A single unit variant of an enum that derives `GlueSqlEnum`
+#[derive(Debug, FromVariant)]
+#[darling(attributes(gluesql))]
+pub struct GluesqlVariant {
+    /// The identifier of this variant.
+    pub ident: syn::Ident,
+    /// Override the text stored for this variant instead of using `self.ident`.
+    pub rename: Option<String>,
+}
+
+impl DeriveGluesqlEnum {
+    /// Provides a slice of this enum's variants.
+    pub fn variants(&self) -> &[GluesqlVariant] {
+        match &self.data {
+            Data::Enum(variants) => variants,
+            _ => panic!("invalid shape"),
+        }
+    }
+
+    /// Validates all variants
+    pub fn validate(&self) -> syn::Result<()> {
+        if self.repr == EnumRepr::Int
+            && (self.rename_all.is_some() || self.variants().iter().any(|v| v.rename.is_some()))
+        {
+            return Err(Error::custom(
+                r#"can't combine `#[gluesql(repr = "int")]` with `rename` or `rename_all`"#,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the text stored for a variant
+    fn variant_name(&self, variant: &GluesqlVariant) -> String {
+        variant.rename.clone().unwrap_or_else(|| {
+            let name = variant.ident.to_string();
+            match self.rename_all {
+                Some(rule) => rule.apply(&name),
+                None => name,
+            }
+        })
+    }
+
+    /// Generate the `ToGlueSql`, `ToGlueSqlValue`, `FromGlueSql` and `ReflectGlueSql` implementations.
+    fn generate_enum(self) -> syn::Result<TokenStream> {
+        self.validate()?;
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let variants = self.variants().iter().map(|v| &v.ident).collect::<Vec<_>>();
+
+        let (storage_ty, to_gluesql, to_gluesql_value, from_gluesql) = match self.repr {
+            EnumRepr::Text => {
+                let names = self
+                    .variants()
+                    .iter()
+                    .map(|v| self.variant_name(v))
+                    .collect::<Vec<_>>();
+                (
+                    quote!(String),
+                    quote! {
+                        match self {
+                            #(Self::#variants => ::gluesql_derive::gluesql_core::ast_builder::text(#names),)*
+                        }
+                    },
+                    quote! {
+                        match self {
+                            #(Self::#variants => ::gluesql_derive::gluesql_core::prelude::Value::Str(#names.to_string()),)*
+                        }
+                    },
+                    quote! {
+                        match value {
+                            ::gluesql_derive::gluesql_core::prelude::Value::Str(s) => match s.as_str() {
+                                #(#names => Ok(Self::#variants),)*
+                                _ => Err(::gluesql_derive::Error::InvalidConversion(
+                                    std::any::type_name::<Self>(),
+                                    ::gluesql_derive::gluesql_core::prelude::Value::Str(s),
+                                )),
+                            },
+                            _ => Err(::gluesql_derive::Error::InvalidConversion(std::any::type_name::<Self>(), value)),
+                        }
+                    },
+                )
+            }
+            EnumRepr::Int => (
+                quote!(i64),
+                quote! {
+                    match self {
+                        #(Self::#variants => ::gluesql_derive::gluesql_core::ast_builder::num(Self::#variants as i64),)*
+                    }
+                },
+                quote! {
+                    match self {
+                        #(Self::#variants => ::gluesql_derive::gluesql_core::prelude::Value::I64(Self::#variants as i64),)*
+                    }
+                },
+                quote! {
+                    match value {
+                        #(::gluesql_derive::gluesql_core::prelude::Value::I64(i) if i == Self::#variants as i64 => Ok(Self::#variants),)*
+                        _ => Err(::gluesql_derive::Error::InvalidConversion(std::any::type_name::<Self>(), value)),
+                    }
+                },
+            ),
+        };
+
+        Ok(quote! {
+            impl #impl_generics ::gluesql_derive::ToGlueSql for #ident #ty_generics #where_clause {
+                fn to_gluesql(&self) -> ::gluesql_derive::gluesql_core::ast_builder::ExprNode<'static> {
+                    #to_gluesql
+                }
+            }
+            impl #impl_generics ::gluesql_derive::ToGlueSqlValue for #ident #ty_generics #where_clause {
+                fn to_gluesql_value(&self) -> ::gluesql_derive::gluesql_core::prelude::Value {
+                    #to_gluesql_value
+                }
+            }
+            impl #impl_generics ::gluesql_derive::FromGlueSql for #ident #ty_generics #where_clause {
+                fn from_gluesql(value: ::gluesql_derive::gluesql_core::prelude::Value) -> Result<Self, ::gluesql_derive::Error> {
+                    #from_gluesql
+                }
+            }
+            impl #impl_generics ::gluesql_derive::ReflectGlueSql for #ident #ty_generics #where_clause {
+                fn reflect_gluesql_type() -> String {
+                    <#storage_ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_type()
+                }
+                fn reflect_gluesql_type_with_nullability() -> String {
+                    <#storage_ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_type_with_nullability()
+                }
+                fn reflect_gluesql_data_type() -> Result<::gluesql_derive::gluesql_core::ast::DataType, ::gluesql_derive::Error> {
+                    <#storage_ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_data_type()
+                }
+            }
+        }
+        .into())
+    }
+}
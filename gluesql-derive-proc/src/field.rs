@@ -1,39 +1,156 @@
 use darling::ast::Data;
-use darling::{Error, FromDeriveInput, FromField};
+use darling::util::SpannedValue;
+use darling::{Error, FromDeriveInput, FromField, FromMeta};
 use proc_macro2::TokenStream as TokenStream2;
-use quote::ToTokens;
+use quote::{format_ident, quote, ToTokens};
 
-/// Main struct for deriving `FromRow` for a struct.
+use crate::rename::RenameRule;
+
+/// Main struct for deriving `FromGlueSqlRow`, `ToGlueSqlRow`, `ToGlueSqlValueRow` and `ReflectGlueSqlRow` for a struct.
 #[derive(Debug, FromDeriveInput)]
 #[darling(
     attributes(gluesql),
     forward_attrs(allow, doc, cfg),
-    supports(struct_named)
+    supports(struct_named, struct_newtype, struct_tuple)
 )]
 pub struct DeriveGluesqlRow {
     pub ident: syn::Ident,
     pub generics: syn::Generics,
     pub data: Data<(), GluesqlField>,
+    /// Secondary indexes declared with `#[gluesql(index(name = "..", columns = ".."))]`.
+    #[darling(default, multiple, rename = "index")]
+    pub indexes: Vec<StructIndex>,
+    /// The table this struct is stored in, declared with `#[gluesql(table = "..")]`.
+    pub table: Option<String>,
+    /// Case conversion applied to every column name that is not explicitly renamed.
+    pub rename_all: Option<RenameRule>,
+    /// Treat a single field struct as its field, implementing the value traits instead of the row traits,
+    /// like `#[derive(GlueSqlValue)]`.
+    #[darling(default)]
+    pub transparent: bool,
+}
+
+/// A secondary index declared on the struct
+#[derive(Debug, FromMeta)]
+pub struct StructIndex {
+    /// The name of the index.
+    pub name: String,
+    /// The indexed column. GlueSQL does not support composite indexes, so this must be a single column.
+    pub columns: SpannedValue<String>,
 }
 
 impl DeriveGluesqlRow {
-    /// Validates all fields
+    /// Validates all fields and resolves their column names
     pub fn validate(&mut self) -> syn::Result<()> {
+        for field in self.fields() {
+            field.validate()?;
+        }
+
+        let rename_all = self.rename_all;
+        if rename_all.is_some_and(|rule| !rule.keeps_identifiers()) {
+            return Err(Error::custom(
+                "kebab case column names aren't valid SQL identifiers, use `snake_case` or `SCREAMING_SNAKE_CASE`",
+            )
+            .into());
+        }
         match &mut self.data {
             Data::Struct(fields) => {
                 fields.fields.iter_mut().enumerate().for_each(|(i, x)| {
-                    x.index = i;
+                    x.position = i;
+                    if let (Some(rule), None, false, Some(ident)) =
+                        (rename_all, &x.rename, x.flatten, &x.ident)
+                    {
+                        x.rename = Some(rule.apply(&ident.to_string()));
+                    }
                 });
             }
             _ => panic!("invalid shape"),
         }
-        for field in self.fields() {
-            field.validate()?;
+
+        if self.transparent {
+            if self.fields().len() != 1 {
+                return Err(Error::custom(
+                    "`#[gluesql(transparent)]` requires a struct with a single field",
+                )
+                .into());
+            }
+            if self.table.is_some() || !self.indexes.is_empty() || self.rename_all.is_some() {
+                return Err(Error::custom(
+                    "can't combine `#[gluesql(transparent)]` with other struct attributes",
+                )
+                .into());
+            }
+            if self.fields()[0].has_attributes() {
+                return Err(self.fields()[0].error(
+                    "the field of a `#[gluesql(transparent)]` struct can't have attributes",
+                ));
+            }
+        }
+
+        if self.fields().iter().filter(|f| f.primary_key).count() > 1 {
+            return Err(Error::custom(
+                "only one field can be marked with `#[gluesql(primary_key)]`",
+            )
+            .into());
+        }
+
+        let mut columns = std::collections::HashSet::new();
+        for field in self.stored_fields().filter(|f| !f.flatten) {
+            let mut names = vec![field.column_name()];
+            if field.timestamp == Some(TimestampEncoding::Offset) {
+                names.push(field.offset_column_name());
+            }
+            for name in names {
+                if !columns.insert(name.clone()) {
+                    return Err(field.error(&format!("duplicate column name `{}`", name)));
+                }
+            }
+        }
+
+        for index in &self.indexes {
+            if index.columns.split(',').count() != 1 {
+                return Err(syn::Error::new(
+                    index.columns.span(),
+                    format!(
+                        "index `{}`: GlueSQL does not support composite indexes",
+                        index.name
+                    ),
+                ));
+            }
+            let column = index.columns.trim();
+            if !columns.contains(column) {
+                return Err(syn::Error::new(
+                    index.columns.span(),
+                    format!("index `{}`: no column named `{}`", index.name, column),
+                ));
+            }
         }
 
         Ok(())
     }
 
+    /// Wether this is a tuple struct, e.g. `struct Pair(i64, String)`.
+    pub fn is_tuple(&self) -> bool {
+        match &self.data {
+            Data::Struct(fields) => fields.is_tuple(),
+            _ => panic!("invalid shape"),
+        }
+    }
+
+    /// Builds `Self` from one expression per field, in declaration order.
+    pub fn construct(&self, values: &[TokenStream2]) -> TokenStream2 {
+        if self.is_tuple() {
+            return quote!(Self(#(#values),*));
+        }
+        let idents = self.fields().iter().map(|f| &f.ident);
+        quote!(Self { #(#idents: #values),* })
+    }
+
+    /// Provides the fields stored in columns, i.e. without `#[gluesql(skip)]`.
+    pub fn stored_fields(&self) -> impl Iterator<Item = &GluesqlField> {
+        self.fields().iter().filter(|f| !f.skip)
+    }
+
     /// Provides a slice of this struct's fields.
     pub fn fields(&self) -> &[GluesqlField] {
         match &self.data {
@@ -42,53 +159,307 @@ impl DeriveGluesqlRow {
         }
     }
 }
-/// A single field inside a struct that derives `FromRow`
+/// Alternative encodings of a timestamp column, selected with `#[gluesql(timestamp = "..")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampEncoding {
+    /// Microseconds since the unix epoch, as `INT`
+    Micros,
+    /// RFC 3339 text keeping the offset, as `TEXT`
+    Rfc3339,
+    /// A `TIMESTAMP` in UTC plus a `{column}_offset` `INT` column
+    Offset,
+}
+
+impl FromMeta for TimestampEncoding {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "micros" => Ok(TimestampEncoding::Micros),
+            "rfc3339" => Ok(TimestampEncoding::Rfc3339),
+            "offset" => Ok(TimestampEncoding::Offset),
+            _ => Err(Error::unknown_value(value)),
+        }
+    }
+}
+
+impl TimestampEncoding {
+    fn name(self) -> &'static str {
+        match self {
+            TimestampEncoding::Micros => "micros",
+            TimestampEncoding::Rfc3339 => "rfc3339",
+            TimestampEncoding::Offset => "offset",
+        }
+    }
+    /// The trait implementing this encoding, e.g. `::gluesql_derive::TimestampMicros`
+    pub fn trait_path(self) -> TokenStream2 {
+        let ident = match self {
+            TimestampEncoding::Micros => format_ident!("TimestampMicros"),
+            TimestampEncoding::Rfc3339 => format_ident!("TimestampRfc3339"),
+            TimestampEncoding::Offset => format_ident!("TimestampOffset"),
+        };
+        quote!(::gluesql_derive::#ident)
+    }
+    /// The function of `trait_path` writing the value, e.g. `to_gluesql_micros`
+    pub fn write_fn(self) -> syn::Ident {
+        format_ident!("to_gluesql_{}", self.name())
+    }
+    /// The function of `trait_path` writing the typed `Value`, e.g. `to_gluesql_micros_value`
+    pub fn write_value_fn(self) -> syn::Ident {
+        format_ident!("to_gluesql_{}_value", self.name())
+    }
+    /// The function of `trait_path` reading the value, e.g. `from_gluesql_micros`
+    pub fn read_fn(self) -> syn::Ident {
+        format_ident!("from_gluesql_{}", self.name())
+    }
+    /// The function of `trait_path` returning the column type(s), e.g. `reflect_gluesql_micros_type_with_nullability`
+    pub fn reflect_fn(self) -> syn::Ident {
+        match self {
+            TimestampEncoding::Offset => {
+                format_ident!("reflect_gluesql_offset_types_with_nullability")
+            }
+            _ => format_ident!("reflect_gluesql_{}_type_with_nullability", self.name()),
+        }
+    }
+}
+
+/// The value of `#[gluesql(default)]` on a field
+#[derive(Debug, Clone)]
+pub enum FieldDefault {
+    /// `#[gluesql(default)]`: read `Default::default()` when the column is missing or `NULL`
+    Trait,
+    /// `#[gluesql(default = "<sql expr>")]`: the `DEFAULT` of the column
+    Sql(String),
+}
+
+impl FromMeta for FieldDefault {
+    fn from_word() -> darling::Result<Self> {
+        Ok(FieldDefault::Trait)
+    }
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(FieldDefault::Sql(value.to_string()))
+    }
+}
+
+/// A single field inside a struct that derives `FromGlueSqlRow`, `ToGlueSqlRow` or `ReflectGlueSqlRow`
 #[derive(Debug, FromField)]
-#[darling(attributes(from_row), forward_attrs(allow, doc, cfg))]
+#[darling(attributes(gluesql), forward_attrs(from_row))]
 pub struct GluesqlField {
-    #[darling(default)]
-    pub index: usize,
+    /// Forwarded `#[from_row(..)]` attributes, only kept to report them as errors.
+    pub attrs: Vec<syn::Attribute>,
+    /// The position of this field in the struct.
+    #[darling(skip)]
+    pub position: usize,
     /// The identifier of this field.
     pub ident: Option<syn::Ident>,
     /// The type specified in this field.
     pub ty: syn::Type,
-    /// Wether to flatten this field. Flattening means calling the `FromRow` implementation
+    /// Wether to flatten this field. Flattening means calling the `FromGlueSqlRow` implementation
     /// of `self.ty` instead of extracting it directly from the row.
     #[darling(default)]
     pub flatten: bool,
-    /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
+    /// Optionaly use this type as the target for `FromGlueSqlRow` or `FromGlueSql`, and then
     /// call `TryFrom::try_from` to convert it the `self.ty`.
     pub try_from: Option<String>,
-    /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
+    /// Optionaly use this type as the target for `FromGlueSqlRow` or `FromGlueSql`, and then
     /// call `From::from` to convert it the `self.ty`.
     pub from: Option<String>,
+    /// Optionaly convert the field into this type with `TryInto::try_into`, and then
+    /// use its `ToGlueSqlRow` or `ToGlueSql` implementation.
+    pub try_into: Option<String>,
+    /// Optionaly convert the field into this type with `Into::into`, and then
+    /// use its `ToGlueSqlRow` or `ToGlueSql` implementation.
+    pub into: Option<String>,
     /// Override the name of the actual sql column instead of using `self.ident`.
     /// Is not compatible with `flatten` since no column is needed there.
     pub rename: Option<String>,
+    /// Declare this column as the `PRIMARY KEY` of the table.
+    #[darling(default)]
+    pub primary_key: bool,
+    /// Declare this column as `UNIQUE`.
+    #[darling(default)]
+    pub unique: bool,
+    /// Create a secondary index on this column.
+    #[darling(default)]
+    pub index: bool,
+    /// Either the SQL expression used as the `DEFAULT` of this column, e.g. `"0"` or `"'active'"`,
+    /// or, as a bare `#[gluesql(default)]`, read `Default::default()` when the column is missing or `NULL`.
+    pub default: Option<FieldDefault>,
+    /// Read the result of this function when the column is missing or `NULL`, like `#[gluesql(default)]`.
+    pub default_with: Option<syn::Path>,
+    /// Don't store this field, it's filled with `Default::default()` or `default_with` on read.
+    #[darling(default)]
+    pub skip: bool,
+    /// Store this timestamp with another encoding than `TIMESTAMP`, see `TimestampEncoding`.
+    pub timestamp: Option<TimestampEncoding>,
+    /// The previous name of the column, so schema migrations rename it instead of dropping it.
+    pub renamed_from: Option<String>,
 }
 
 impl GluesqlField {
     /// Checks wether this field has a valid combination of attributes
     pub fn validate(&self) -> syn::Result<()> {
-        if self.from.is_some() && self.try_from.is_some() {
+        if let Some(attr) = self.attrs.iter().find(|a| a.path().is_ident("from_row")) {
             return Err(Error::custom(
-                r#"can't combine `#[from_row(from = "..")]` with `#[from_row(try_from = "..")]`"#,
+                "`#[from_row(..)]` is not supported, use `#[gluesql(..)]` instead",
             )
+            .with_span(attr)
             .into());
         }
 
+        if self.from.is_some() && self.try_from.is_some() {
+            return Err(self.error(
+                r#"can't combine `#[gluesql(from = "..")]` with `#[gluesql(try_from = "..")]`"#,
+            ));
+        }
+
+        if self.into.is_some() && self.try_into.is_some() {
+            return Err(self.error(
+                r#"can't combine `#[gluesql(into = "..")]` with `#[gluesql(try_into = "..")]`"#,
+            ));
+        }
+
         if self.rename.is_some() && self.flatten {
-            return Err(Error::custom(
-                r#"can't combine `#[from_row(flatten)]` with `#[from_row(rename = "..")]`"#,
-            )
-            .into());
+            return Err(self
+                .error(r#"can't combine `#[gluesql(flatten)]` with `#[gluesql(rename = "..")]`"#));
+        }
+
+        if self.skip
+            && (self.flatten
+                || self.try_from.is_some()
+                || self.from.is_some()
+                || self.try_into.is_some()
+                || self.into.is_some()
+                || self.rename.is_some()
+                || self.primary_key
+                || self.unique
+                || self.index
+                || self.sql_default().is_some()
+                || self.timestamp.is_some()
+                || self.renamed_from.is_some())
+        {
+            return Err(self.error(
+                "`#[gluesql(skip)]` can only be combined with `#[gluesql(default)]` or `#[gluesql(default_with = \"..\")]`",
+            ));
+        }
+
+        if matches!(self.default, Some(FieldDefault::Trait)) && self.default_with.is_some() {
+            return Err(self.error(
+                r#"can't combine `#[gluesql(default)]` with `#[gluesql(default_with = "..")]`"#,
+            ));
+        }
+
+        if self.has_fallback() && !self.skip && (self.flatten || self.timestamp.is_some()) {
+            return Err(self.error(
+                r#"`#[gluesql(default)]` and `#[gluesql(default_with = "..")]` can't be combined with `#[gluesql(flatten)]` or `#[gluesql(timestamp = "..")]`"#,
+            ));
+        }
+
+        if self.flatten
+            && (self.primary_key || self.unique || self.index || self.sql_default().is_some())
+        {
+            return Err(self.error("can't combine `#[gluesql(flatten)]` with column constraints"));
+        }
+
+        if self.flatten && self.renamed_from.is_some() {
+            return Err(self.error(
+                r#"can't combine `#[gluesql(flatten)]` with `#[gluesql(renamed_from = "..")]`"#,
+            ));
+        }
+
+        if self.flatten && self.timestamp.is_some() {
+            return Err(self.error(
+                r#"can't combine `#[gluesql(flatten)]` with `#[gluesql(timestamp = "..")]`"#,
+            ));
+        }
+
+        if self.timestamp == Some(TimestampEncoding::Offset)
+            && (self.primary_key || self.unique || self.index || self.sql_default().is_some())
+        {
+            return Err(self.error(
+                r#"can't combine `#[gluesql(timestamp = "offset")]` with column constraints"#,
+            ));
+        }
+
+        if self.primary_key && self.unique {
+            return Err(
+                self.error("can't combine `#[gluesql(primary_key)]` with `#[gluesql(unique)]`")
+            );
         }
 
         Ok(())
     }
 
+    /// Wether any `#[gluesql(..)]` attribute is set on this field
+    pub fn has_attributes(&self) -> bool {
+        self.flatten
+            || self.try_from.is_some()
+            || self.from.is_some()
+            || self.try_into.is_some()
+            || self.into.is_some()
+            || self.rename.is_some()
+            || self.primary_key
+            || self.unique
+            || self.index
+            || self.default.is_some()
+            || self.default_with.is_some()
+            || self.skip
+            || self.timestamp.is_some()
+            || self.renamed_from.is_some()
+    }
+
+    /// Returns the SQL `DEFAULT` of the column set by `#[gluesql(default = "..")]`
+    pub fn sql_default(&self) -> Option<&str> {
+        match &self.default {
+            Some(FieldDefault::Sql(default)) => Some(default),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DEFAULT` of the column as an `Option<&'static SqlDefault>`, evaluated once at runtime
+    pub fn sql_default_static(&self) -> TokenStream2 {
+        match self.sql_default() {
+            Some(default) => quote!(Some({
+                static DEFAULT: ::gluesql_derive::SqlDefault = ::gluesql_derive::SqlDefault::new(#default);
+                &DEFAULT
+            })),
+            None => quote!(None),
+        }
+    }
+
+    /// Whether a missing or `NULL` column is read as `Default::default()` or `default_with`
+    pub fn has_fallback(&self) -> bool {
+        matches!(self.default, Some(FieldDefault::Trait)) || self.default_with.is_some()
+    }
+
+    /// Returns the expression filling this field when the column is missing or `NULL`, or skipped
+    pub fn fallback(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.default_with {
+            Some(path) => quote!(#path()),
+            None => quote!(<#ty as ::std::default::Default>::default()),
+        }
+    }
+
+    /// Returns the expression accessing this field on `self`, e.g. `self.id` or `self.0`
+    pub fn member(&self) -> TokenStream2 {
+        match &self.ident {
+            Some(ident) => quote!(self.#ident),
+            None => {
+                let index = syn::Index::from(self.position);
+                quote!(self.#index)
+            }
+        }
+    }
+
+    /// Returns an error pointing at this field
+    pub fn error(&self, message: &str) -> syn::Error {
+        match &self.ident {
+            Some(ident) => Error::custom(message).with_span(ident).into(),
+            None => Error::custom(message).with_span(&self.ty).into(),
+        }
+    }
+
     /// Returns a tokenstream of the type that should be returned from either
-    /// `FromRow` (when using `flatten`) or `FromSql`.
+    /// `FromGlueSqlRow` (when using `flatten`) or `FromGlueSql`.
     pub fn target_ty(&self) -> syn::Result<TokenStream2> {
         if let Some(from) = &self.from {
             Ok(from.parse()?)
@@ -99,12 +470,80 @@ impl GluesqlField {
         }
     }
 
+    /// Returns a tokenstream of the type whose `ToGlueSqlRow` (when using `flatten`) or `ToGlueSql`
+    /// implementation is used to write this field.
+    pub fn write_ty(&self) -> syn::Result<TokenStream2> {
+        if let Some(into) = &self.into {
+            Ok(into.parse()?)
+        } else if let Some(try_into) = &self.try_into {
+            Ok(try_into.parse()?)
+        } else {
+            Ok(self.ty.to_token_stream())
+        }
+    }
+
+    /// Returns a tokenstream of the type stored in the column of this field:
+    /// the `[try_]from` type if any, then the `[try_]into` type, then `self.ty`.
+    pub fn column_ty(&self) -> syn::Result<TokenStream2> {
+        if self.from.is_some() || self.try_from.is_some() {
+            self.target_ty()
+        } else {
+            self.write_ty()
+        }
+    }
+
+    /// Returns the column constraints appended to the column definition in the DDL
+    pub fn constraints(&self) -> String {
+        let mut constraints = String::new();
+        if let Some(default) = self.sql_default() {
+            constraints.push_str(" DEFAULT ");
+            constraints.push_str(default);
+        }
+        if self.primary_key {
+            constraints.push_str(" PRIMARY KEY");
+        }
+        if self.unique {
+            constraints.push_str(" UNIQUE");
+        }
+        constraints
+    }
+
+    /// Returns the `DEFAULT` expression of the column as an `Option<Expr>`, parsed at runtime
+    pub fn schema_default(&self) -> TokenStream2 {
+        match self.sql_default() {
+            Some(default) => quote! {
+                Some(::gluesql_derive::gluesql_core::translate::translate_expr(
+                    &::gluesql_derive::gluesql_core::parse_sql::parse_expr(#default)?,
+                )?)
+            },
+            None => quote!(None),
+        }
+    }
+
+    /// Returns the `PRIMARY KEY` / `UNIQUE` constraint of the column as an `Option<ColumnUniqueOption>`
+    pub fn schema_unique(&self) -> TokenStream2 {
+        if self.primary_key || self.unique {
+            let is_primary = self.primary_key;
+            quote! {
+                Some(::gluesql_derive::gluesql_core::ast::ColumnUniqueOption { is_primary: #is_primary })
+            }
+        } else {
+            quote!(None)
+        }
+    }
+
     /// Returns the name that maps to the actuall sql column
-    /// By default this is the same as the rust field name but can be overwritten by `#[from_row(rename = "..")]`.
+    /// By default this is the same as the rust field name, or `c{position}` for tuple structs,
+    /// but can be overwritten by `#[gluesql(rename = "..")]` or the struct level `#[gluesql(rename_all = "..")]`.
     pub fn column_name(&self) -> String {
-        self.rename
-            .as_ref()
-            .map(Clone::clone)
-            .unwrap_or_else(|| self.ident.as_ref().unwrap().to_string())
+        self.rename.clone().unwrap_or_else(|| match &self.ident {
+            Some(ident) => ident.to_string(),
+            None => format!("c{}", self.position),
+        })
+    }
+
+    /// Returns the name of the offset column added by `#[gluesql(timestamp = "offset")]`
+    pub fn offset_column_name(&self) -> String {
+        format!("{}_offset", self.column_name())
     }
 }
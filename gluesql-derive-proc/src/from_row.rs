@@ -5,7 +5,7 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::DeriveInput;
 
-use crate::field::{DeriveGluesqlRow, GluesqlField};
+use crate::field::{DeriveGluesqlRow, GluesqlField, TimestampEncoding};
 
 /// Fallible entry point for generating a `FromRow` implementation
 pub fn try_derive_from_row(input: &DeriveInput) -> Result<TokenStream, Error> {
@@ -17,11 +17,14 @@ impl DeriveGluesqlRow {
     /// Generate the `FromRow` implementation.
     fn generate_from_row(mut self) -> syn::Result<TokenStream> {
         self.validate()?;
+        if self.transparent {
+            return Ok(self.generate_from_value()?.into());
+        }
 
         let ident = &self.ident;
 
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let original_predicates = where_clause.clone().map(|w| &w.predicates).into_iter();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
         let predicates = self.predicates_from()?;
 
         let from_row_fields = self
@@ -29,16 +32,26 @@ impl DeriveGluesqlRow {
             .iter()
             .map(|f| f.generate_from_row())
             .collect::<syn::Result<Vec<_>>>()?;
+        let construct = self.construct(&from_row_fields);
+        let from_map_fields = self
+            .fields()
+            .iter()
+            .map(|f| f.generate_from_map())
+            .collect::<syn::Result<Vec<_>>>()?;
+        let construct_from_map = self.construct(&from_map_fields);
 
         Ok(quote! {
             impl #impl_generics ::gluesql_derive::FromGlueSqlRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
-                fn from_gluesql_row(labels: &[String], row: Vec<::gluesql_derive::gluesql_core::prelude::Value>) -> Result<Self, ::gluesql_derive::Error> {
-                    let mut row = row.into_iter();
-                    let this = Self {
-                        #(#from_row_fields), *
-                    };
-                    drop(row);
-                    Ok(this)
+                fn from_gluesql_row_indexed(
+                    labels: &::gluesql_derive::LabelIndex,
+                    row: &mut [::gluesql_derive::gluesql_core::prelude::Value],
+                ) -> Result<Self, ::gluesql_derive::Error> {
+                    Ok(#construct)
+                }
+                fn from_gluesql_map_entries(
+                    map: &mut ::std::collections::HashMap<String, ::gluesql_derive::gluesql_core::prelude::Value>,
+                ) -> Result<Self, ::gluesql_derive::Error> {
+                    Ok(#construct_from_map)
                 }
 
             }
@@ -59,44 +72,129 @@ impl DeriveGluesqlRow {
 }
 
 impl GluesqlField {
-    /// Generate the line needed to retrievee this field from a row when calling `from_row`.
+    /// Generate the expression needed to retrievee this field from a row when calling `from_row`.
+    /// A column missing from the result set is an error, its `DEFAULT` says nothing about the stored value.
     fn generate_from_row(&self) -> syn::Result<TokenStream2> {
-        let ident = self.ident.as_ref().unwrap();
+        if self.skip {
+            return Ok(self.fallback());
+        }
         let column_name = self.column_name();
-        let index = self.index;
-        let field_ty = &self.ty;
         let target_ty = self.target_ty()?;
 
-        let mut base = if self.flatten {
-            unimplemented!("flatten field not supported")
+        if self.has_fallback() {
+            let fallback = self.fallback();
+            let converted = self.convert_from(
+                quote!(<#target_ty as ::gluesql_derive::FromGlueSql>::from_gluesql(value)?),
+            )?;
+            return Ok(quote! {
+                match labels.take_optional(row, #column_name)? {
+                    Some(value) => #converted,
+                    None => #fallback,
+                }
+            });
+        }
+
+        let base = if self.flatten {
+            quote!(<#target_ty as ::gluesql_derive::FromGlueSqlRow>::from_gluesql_row_indexed(labels, row)?)
+        } else if let Some(encoding) = self.timestamp {
+            let trait_path = encoding.trait_path();
+            let read_fn = encoding.read_fn();
+            if encoding == TimestampEncoding::Offset {
+                let offset_column_name = self.offset_column_name();
+                quote!(<#target_ty as #trait_path>::#read_fn(labels.take(row, #column_name)?, labels.take(row, #offset_column_name)?)?)
+            } else {
+                quote!(<#target_ty as #trait_path>::#read_fn(labels.take(row, #column_name)?)?)
+            }
         } else {
-            quote!({
-                if labels.get(#index).map(|x| x.as_str()) != Some(#column_name) {
-                    return Err(::gluesql_derive::Error::InvalidFieldName(#index, #column_name, labels.get(#index).cloned().unwrap_or_default()));
+            quote!(<#target_ty as ::gluesql_derive::FromGlueSql>::from_gluesql(labels.take(row, #column_name)?)?)
+        };
+
+        self.convert_from(base)
+    }
+
+    /// Generate the expression needed to retrieve this field from a schemaless row when calling `from_gluesql_map`.
+    /// A missing column gets its `DEFAULT`, or is read as `NULL` when the field accepts it.
+    fn generate_from_map(&self) -> syn::Result<TokenStream2> {
+        if self.skip {
+            return Ok(self.fallback());
+        }
+        let column_name = self.column_name();
+        let target_ty = self.target_ty()?;
+        let default = self.sql_default_static();
+
+        if self.has_fallback() {
+            let fallback = self.fallback();
+            let converted = self.convert_from(
+                quote!(<#target_ty as ::gluesql_derive::FromGlueSql>::from_gluesql(value)?),
+            )?;
+            return Ok(quote! {
+                match ::gluesql_derive::take_map_value(map, #column_name, #default)? {
+                    None | Some(::gluesql_derive::gluesql_core::prelude::Value::Null) => #fallback,
+                    Some(value) => #converted,
                 }
-                row.next().ok_or(::gluesql_derive::Error::InvalidExtract(#index, #column_name))?
-            })
+            });
+        }
+
+        let take = |column: &str| {
+            quote! {
+                ::gluesql_derive::take_map_value(map, #column, #default)?
+                    .unwrap_or(::gluesql_derive::gluesql_core::prelude::Value::Null)
+            }
         };
 
+        let base = if self.flatten {
+            quote!(<#target_ty as ::gluesql_derive::FromGlueSqlRow>::from_gluesql_map_entries(map)?)
+        } else if let Some(encoding) = self.timestamp {
+            let trait_path = encoding.trait_path();
+            let read_fn = encoding.read_fn();
+            let value = take(&column_name);
+            if encoding == TimestampEncoding::Offset {
+                let offset = take(&self.offset_column_name());
+                quote!(<#target_ty as #trait_path>::#read_fn(#value, #offset)?)
+            } else {
+                quote!(<#target_ty as #trait_path>::#read_fn(#value)?)
+            }
+        } else {
+            quote! {
+                match ::gluesql_derive::take_map_value(map, #column_name, #default)? {
+                    Some(value) => <#target_ty as ::gluesql_derive::FromGlueSql>::from_gluesql(value)?,
+                    None => <#target_ty as ::gluesql_derive::FromGlueSql>::from_gluesql(::gluesql_derive::gluesql_core::prelude::Value::Null)
+                        .map_err(|_| ::gluesql_derive::Error::MissingColumn(#column_name.to_string()))?,
+                }
+            }
+        };
+
+        self.convert_from(base)
+    }
+
+    /// Wraps the expression reading the column with the `from` / `try_from` conversion into the field type, if any.
+    fn convert_from(&self, mut base: TokenStream2) -> syn::Result<TokenStream2> {
+        let column_name = self.column_name();
+        let field_ty = &self.ty;
+        let target_ty = self.target_ty()?;
+
         if self.from.is_some() {
             base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
         } else if self.try_from.is_some() {
-            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base).expect("could not convert column"));
-        } else {
-            base = quote!(<#field_ty as ::gluesql_derive::FromGlueSql>::from_gluesql(#base)?);
+            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base)
+                .map_err(|e| ::gluesql_derive::Error::ConversionFailed { column: #column_name, source: e.into() })?);
         }
 
-        Ok(quote!(#ident: #base))
+        Ok(base)
     }
 }
 impl GluesqlField {
     /// Pushes the needed where clause predicates for this field.
     ///
-    /// By default this is `T: postgres::types::FromSql`,
-    /// when using `flatten` it's: `T: postgres_from_row::FromRow`
+    /// By default this is `T: gluesql_derive::FromGlueSql`,
+    /// when using `flatten` it's: `T: gluesql_derive::FromGlueSqlRow`,
+    /// when using `timestamp = ".."` it's the trait of the encoding, e.g. `T: gluesql_derive::TimestampMicros`
     /// and when using either `from` or `try_from` attributes it additionally pushes this bound:
-    /// `T: std::convert::From<R>`, where `T` is the type specified in the struct and `R` is the
+    /// `T: std::convert::[Try]From<R>`, where `T` is the type specified in the struct and `R` is the
     /// type specified in the `[try]_from` attribute.
+    /// The error of `TryFrom` must be convertible into `Box<dyn std::error::Error + Send + Sync>`.
+    /// A field read as `Default::default()` with `skip` or `default` also needs `F: Default`,
+    /// and a `skip` field needs nothing else.
     pub fn add_predicates_from(
         &self,
         predicates: &mut Vec<syn::__private::TokenStream2>,
@@ -104,8 +202,18 @@ impl GluesqlField {
         let target_ty = &self.target_ty()?;
         let ty = &self.ty;
 
+        if (self.skip || self.has_fallback()) && self.default_with.is_none() {
+            predicates.push(quote!(#ty: ::std::default::Default));
+        }
+        if self.skip {
+            return Ok(());
+        }
+
         predicates.push(if self.flatten {
-            quote! (#target_ty: ::gluesql_derive::FromRow)
+            quote! (#target_ty: ::gluesql_derive::FromGlueSqlRow)
+        } else if let Some(encoding) = self.timestamp {
+            let trait_path = encoding.trait_path();
+            quote! (#target_ty: #trait_path)
         } else {
             quote! (#target_ty: ::gluesql_derive::FromGlueSql)
         });
@@ -116,10 +224,7 @@ impl GluesqlField {
             let try_from = quote!(std::convert::TryFrom<#target_ty>);
 
             predicates.push(quote!(#ty: #try_from));
-            predicates.push(
-                quote!(::gluesql_derive::Error: std::convert::From<<#ty as #try_from>::Error>),
-            );
-            predicates.push(quote!(<#ty as #try_from>::Error: std::fmt::Debug));
+            predicates.push(quote!(<#ty as #try_from>::Error: std::convert::Into<Box<dyn std::error::Error + Send + Sync>>));
         }
 
         Ok(())
@@ -1,19 +1,22 @@
 use proc_macro::TokenStream;
 
 use crate::from_row::try_derive_from_row;
-use crate::to_row::try_derive_to_row;
+use crate::to_row::{try_derive_to_row, try_derive_to_value_row};
 use syn::{parse_macro_input, DeriveInput};
 
+mod enum_value;
 mod field;
 mod from_row;
 mod reflect_row;
+mod rename;
 mod to_row;
+mod value;
 
 #[proc_macro_derive(ReflectGlueSqlRow, attributes(gluesql))]
 pub fn derive_reflect_row(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     reflect_row::try_derive_reflect_row(&derive_input)
-        .unwrap_or_else(|err| err.write_errors().into())
+        .unwrap_or_else(|err| err.write_errors())
         .into()
 }
 
@@ -28,3 +31,23 @@ pub fn derive_to_from_row(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     try_derive_to_row(&derive_input).unwrap_or_else(|err| err.write_errors().into())
 }
+
+#[proc_macro_derive(ToGlueSqlValueRow, attributes(gluesql))]
+pub fn derive_to_value_row(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    try_derive_to_value_row(&derive_input).unwrap_or_else(|err| err.write_errors().into())
+}
+
+#[proc_macro_derive(GlueSqlEnum, attributes(gluesql))]
+pub fn derive_enum(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    enum_value::try_derive_enum(&derive_input).unwrap_or_else(|err| err.write_errors().into())
+}
+
+#[proc_macro_derive(GlueSqlValue, attributes(gluesql))]
+pub fn derive_value(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    value::try_derive_value(&derive_input)
+        .unwrap_or_else(|err| err.write_errors())
+        .into()
+}
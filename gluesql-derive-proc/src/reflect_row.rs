@@ -4,81 +4,293 @@ use darling::{Error, FromDeriveInput};
 use quote::quote;
 use syn::DeriveInput;
 
-use crate::field::{DeriveGluesqlRow, GluesqlField};
+use crate::field::{DeriveGluesqlRow, GluesqlField, TimestampEncoding};
 
-/// Fallible entry point for generating a `ToRow` implementation
+/// Fallible entry point for generating a `ReflectGlueSqlRow` implementation
 pub fn try_derive_reflect_row(input: &DeriveInput) -> Result<TokenStream, Error> {
     let from_row_derive = DeriveGluesqlRow::from_derive_input(input)?;
     Ok(from_row_derive.generate_reflect_row()?)
 }
 
 impl DeriveGluesqlRow {
-    /// Generate the DDL for the struct.
+    /// Generate the column definitions used in the DDL for the struct.
     /// example:
-    /// CREATE TABLE IF NOT EXISTS {} (
-    ///    id UINT64 NOT NULL,
-    ///    username TEXT NOT NULL
-    /// );
+    /// vec![
+    ///    "id UINT64 NOT NULL".to_string(),
+    ///    "username TEXT NOT NULL".to_string(),
+    /// ]
     ///
-    fn get_ddl(&self) -> TokenStream {
-        let fields = self
-            .fields()
-            .iter()
-            .map(|f| f.ident.as_ref().unwrap().to_string());
-        let tys = self.fields().iter().map(|f| f.ty.clone());
+    fn get_column_defs(&self) -> syn::Result<TokenStream> {
+        let column_defs = self
+            .stored_fields()
+            .map(|f| {
+                if f.flatten {
+                    let ty = &f.ty;
+                    Ok(quote! {
+                        column_defs.extend(<#ty as ::gluesql_derive::ReflectGlueSqlRow>::column_defs());
+                    })
+                } else {
+                    let ty = f.column_ty()?;
+                    let name = f.column_name();
+                    let constraints = f.constraints();
+                    if let Some(encoding) = f.timestamp {
+                        let trait_path = encoding.trait_path();
+                        let reflect_fn = encoding.reflect_fn();
+                        if encoding == TimestampEncoding::Offset {
+                            let offset_name = f.offset_column_name();
+                            return Ok(quote! {{
+                                let [timestamp, offset] = <#ty as #trait_path>::#reflect_fn();
+                                column_defs.push(format!("{} {}", #name, timestamp));
+                                column_defs.push(format!("{} {}", #offset_name, offset));
+                            }});
+                        }
+                        return Ok(quote! {
+                            column_defs.push(format!("{} {}{}", #name, <#ty as #trait_path>::#reflect_fn(), #constraints));
+                        });
+                    }
+                    Ok(quote! {
+                        column_defs.push(format!("{} {}{}", #name, <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_type_with_nullability(), #constraints));
+                    })
+                }
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
 
+        Ok(quote! {{
+            let mut column_defs = Vec::new();
+            #(#column_defs)*
+            column_defs
+        }})
+    }
+    /// Generate the structured column definitions, mirroring `get_column_defs`.
+    /// example:
+    /// vec![
+    ///    ColumnDef { name: "id".to_string(), data_type: DataType::Uint64, nullable: false, default: None, unique: None },
+    /// ]
+    ///
+    fn get_schema_column_defs(&self) -> syn::Result<TokenStream> {
+        let column_defs = self
+            .stored_fields()
+            .map(|f| {
+                if f.flatten {
+                    let ty = &f.ty;
+                    return Ok(quote! {
+                        column_defs.extend(<#ty as ::gluesql_derive::ReflectGlueSqlRow>::schema_column_defs()?);
+                    });
+                }
+                let ty = f.column_ty()?;
+                let name = f.column_name();
+                let default = f.schema_default();
+                let unique = f.schema_unique();
+                let column_def = quote! {
+                    ::gluesql_derive::gluesql_core::ast::ColumnDef {
+                        name: #name.to_string(),
+                        data_type,
+                        nullable,
+                        default: #default,
+                        unique: #unique,
+                    }
+                };
+                let Some(encoding) = f.timestamp else {
+                    return Ok(quote! {{
+                        let data_type = <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_data_type()?;
+                        let nullable = <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_nullable();
+                        column_defs.push(#column_def);
+                    }});
+                };
+                let trait_path = encoding.trait_path();
+                let reflect_fn = encoding.reflect_fn();
+                if encoding == TimestampEncoding::Offset {
+                    let offset_name = f.offset_column_name();
+                    return Ok(quote! {{
+                        let [timestamp, offset] = <#ty as #trait_path>::#reflect_fn();
+                        let (data_type, nullable) = ::gluesql_derive::parse_column_type(&timestamp)?;
+                        column_defs.push(#column_def);
+                        let (data_type, nullable) = ::gluesql_derive::parse_column_type(&offset)?;
+                        column_defs.push(::gluesql_derive::gluesql_core::ast::ColumnDef {
+                            name: #offset_name.to_string(),
+                            data_type,
+                            nullable,
+                            default: None,
+                            unique: None,
+                        });
+                    }});
+                }
+                Ok(quote! {{
+                    let (data_type, nullable) = ::gluesql_derive::parse_column_type(&<#ty as #trait_path>::#reflect_fn())?;
+                    column_defs.push(#column_def);
+                }})
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(quote! {{
+            let mut column_defs = Vec::new();
+            #(#column_defs)*
+            Ok(column_defs)
+        }})
+    }
+    fn get_columns(&self) -> TokenStream {
+        let columns = self.stored_fields().map(|f| {
+            let ty = &f.ty;
+            if f.flatten {
+                quote! {
+                    columns.extend(<#ty as ::gluesql_derive::ReflectGlueSqlRow>::columns());
+                }
+            } else if f.timestamp == Some(TimestampEncoding::Offset) {
+                let name = f.column_name();
+                let offset_name = f.offset_column_name();
+                quote! {
+                    columns.push(#name);
+                    columns.push(#offset_name);
+                }
+            } else {
+                let name = f.column_name();
+                quote! {
+                    columns.push(#name);
+                }
+            }
+        });
         quote! {{
-            let mut ddl = "".to_string();
-            ddl.push_str("CREATE TABLE IF NOT EXISTS ");
-            ddl.push_str(table);
-            ddl.push_str(" (\n");
-            #(
-                ddl.push_str(#fields);
-                ddl.push_str(" ");
-                ddl.push_str(&<#tys as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_type_with_nullability());
-            ) {
-                ddl.push_str(", \n");
-            } *
-            ddl.push_str(");");
-            ddl
+            let mut columns = Vec::new();
+            #(#columns)*
+            columns
         }}
     }
-    fn get_columns(&self) -> TokenStream {
-        let fields = self.fields();
-        let columns = fields.iter().map(|f| f.ident.as_ref().unwrap().to_string());
+    /// Generate the name of the primary key column, looking into flattened fields
+    /// when the struct itself has no field marked with `#[gluesql(primary_key)]`.
+    fn get_primary_key(&self) -> TokenStream {
+        if let Some(f) = self.stored_fields().find(|f| f.primary_key) {
+            let name = f.column_name();
+            return quote!(Some(#name));
+        }
+        let flattened = self
+            .stored_fields()
+            .filter(|f| f.flatten)
+            .map(|f| &f.ty)
+            .collect::<Vec<_>>();
         quote! {
-            vec![#(#columns),*]
+            None #(.or_else(<#flattened as ::gluesql_derive::ReflectGlueSqlRow>::primary_key))*
         }
     }
-    /// Generate the `FromRow` implementation.
+    /// Generate the `(old, new)` names of the renamed columns, including the offset column of
+    /// `#[gluesql(timestamp = "offset")]` fields.
+    fn get_renamed_columns(&self) -> TokenStream {
+        let renamed = self.stored_fields().filter_map(|f| {
+            let ty = &f.ty;
+            if f.flatten {
+                return Some(quote! {
+                    renamed.extend(<#ty as ::gluesql_derive::ReflectGlueSqlRow>::renamed_columns());
+                });
+            }
+            let from = f.renamed_from.as_ref()?;
+            let name = f.column_name();
+            if f.timestamp == Some(TimestampEncoding::Offset) {
+                let from_offset = format!("{}_offset", from);
+                let offset_name = f.offset_column_name();
+                return Some(quote! {
+                    renamed.push((#from, #name));
+                    renamed.push((#from_offset, #offset_name));
+                });
+            }
+            Some(quote! {
+                renamed.push((#from, #name));
+            })
+        });
+        quote! {{
+            let mut renamed = Vec::new();
+            #(#renamed)*
+            renamed
+        }}
+    }
+    /// Generate the `CREATE INDEX` statements for the struct.
+    /// example:
+    /// CREATE INDEX idx_{table}_username ON {table} (username);
+    ///
+    fn get_index_ddl(&self) -> TokenStream {
+        let indexes = self.stored_fields().filter_map(|f| {
+            let ty = &f.ty;
+            if f.flatten {
+                Some(quote! {
+                    ddl.extend(<#ty as ::gluesql_derive::ReflectGlueSqlRow>::index_ddl(table));
+                })
+            } else if f.index {
+                let name = f.column_name();
+                Some(quote! {
+                    ddl.push(format!("CREATE INDEX idx_{}_{} ON {} ({});", table, #name, table, #name));
+                })
+            } else {
+                None
+            }
+        });
+        let struct_indexes = self.indexes.iter().map(|index| {
+            let name = &index.name;
+            let column = index.columns.trim();
+            quote! {
+                ddl.push(format!("CREATE INDEX {} ON {} ({});", #name, table, #column));
+            }
+        });
+        quote! {{
+            let mut ddl = Vec::new();
+            #(#indexes)*
+            #(#struct_indexes)*
+            ddl
+        }}
+    }
+    /// Generate the `ReflectGlueSqlRow` implementation.
     fn generate_reflect_row(mut self) -> syn::Result<TokenStream> {
         self.validate()?;
+        if self.transparent {
+            return self.generate_reflect_value();
+        }
 
         let ident = &self.ident;
 
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let original_predicates = where_clause.clone().map(|w| &w.predicates).into_iter();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
         let predicates = self.predicates_reflect()?;
 
-        let ddl = self.get_ddl();
+        let column_defs = self.get_column_defs()?;
+        let schema_column_defs = self.get_schema_column_defs()?;
         let columns = self.get_columns();
+        let primary_key = self.get_primary_key();
+        let index_ddl = self.get_index_ddl();
+        let renamed_columns = self.get_renamed_columns();
+        let table = self.table.as_ref().map(|table| {
+            let original_predicates = original_predicates.clone();
+            quote! {
+                impl #impl_generics ::gluesql_derive::GlueSqlTable for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
+                    const TABLE_NAME: &'static str = #table;
+                }
+            }
+        });
         Ok(quote! {
             impl #impl_generics ::gluesql_derive::ReflectGlueSqlRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
-                fn get_ddl(table: &str) -> String {
-                    #ddl
+                fn column_defs() -> Vec<String> {
+                    #column_defs
                 }
                 fn columns() -> Vec<&'static str> {
                     #columns
                 }
+                fn primary_key() -> Option<&'static str> {
+                    #primary_key
+                }
+                fn index_ddl(table: &str) -> Vec<String> {
+                    #index_ddl
+                }
+                fn renamed_columns() -> Vec<(&'static str, &'static str)> {
+                    #renamed_columns
+                }
+                fn schema_column_defs() -> Result<Vec<::gluesql_derive::gluesql_core::ast::ColumnDef>, ::gluesql_derive::Error> {
+                    #schema_column_defs
+                }
             }
-        }
-        .into())
+            #table
+        })
     }
     /// Generates any additional where clause predicates needed for the fields in this struct.
     pub fn predicates_reflect(&self) -> syn::Result<Vec<syn::__private::TokenStream2>> {
         let mut predicates = Vec::new();
 
-        for field in self.fields() {
+        for field in self.stored_fields() {
             field.add_predicates_reflect(&mut predicates)?;
         }
 
@@ -88,21 +300,25 @@ impl DeriveGluesqlRow {
 impl GluesqlField {
     /// Pushes the needed where clause predicates for this field.
     ///
-    /// By default this is `T: postgres::types::FromSql`,
-    /// when using `flatten` it's: `T: postgres_from_row::FromRow`
-    /// and when using either `from` or `try_from` attributes it additionally pushes this bound:
-    /// `T: std::convert::From<R>`, where `T` is the type specified in the struct and `R` is the
-    /// type specified in the `[try]_from` attribute.
+    /// By default this is `T: gluesql_derive::ReflectGlueSql`, where `T` is the type specified
+    /// in the `[try_]from` or `[try_]into` attribute if any, since that is the type stored in the column,
+    /// when using `flatten` it's: `T: gluesql_derive::ReflectGlueSqlRow`
+    /// and when using `timestamp = ".."` it's the trait of the encoding, e.g. `T: gluesql_derive::TimestampMicros`
     pub fn add_predicates_reflect(
         &self,
         predicates: &mut Vec<syn::__private::TokenStream2>,
     ) -> syn::Result<()> {
-        let target_ty = &self.target_ty()?;
+        let ty = &self.ty;
 
         predicates.push(if self.flatten {
-            quote! (#target_ty: ::gluesql_derive::ReflectGlueSqlRow)
+            quote! (#ty: ::gluesql_derive::ReflectGlueSqlRow)
+        } else if let Some(encoding) = self.timestamp {
+            let column_ty = self.column_ty()?;
+            let trait_path = encoding.trait_path();
+            quote! (#column_ty: #trait_path)
         } else {
-            quote! (#target_ty: ::gluesql_derive::ReflectGlueSql)
+            let column_ty = self.column_ty()?;
+            quote! (#column_ty: ::gluesql_derive::ReflectGlueSql)
         });
 
         Ok(())
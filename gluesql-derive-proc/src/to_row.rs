@@ -5,7 +5,7 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::DeriveInput;
 
-use crate::field::{DeriveGluesqlRow, GluesqlField};
+use crate::field::{DeriveGluesqlRow, GluesqlField, TimestampEncoding};
 
 /// Fallible entry point for generating a `ToRow` implementation
 pub fn try_derive_to_row(input: &DeriveInput) -> Result<TokenStream, Error> {
@@ -13,40 +13,90 @@ pub fn try_derive_to_row(input: &DeriveInput) -> Result<TokenStream, Error> {
     Ok(from_row_derive.generate_to_row()?)
 }
 
+/// Fallible entry point for generating a `ToGlueSqlValueRow` implementation
+pub fn try_derive_to_value_row(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let to_value_row_derive = DeriveGluesqlRow::from_derive_input(input)?;
+    Ok(to_value_row_derive.generate_to_value_row()?)
+}
+
 impl DeriveGluesqlRow {
     /// Generate the `ToRow` implementation.
     fn generate_to_row(mut self) -> syn::Result<TokenStream> {
         self.validate()?;
+        if self.transparent {
+            return Ok(self.generate_to_gluesql()?.into());
+        }
 
         let ident = &self.ident;
 
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let original_predicates = where_clause.clone().map(|w| &w.predicates).into_iter();
-        let predicates = self.predicates_to()?;
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+        let predicates = self.predicates_to(false)?;
 
         let to_row_fields = self
-            .fields()
-            .iter()
-            .map(|f| f.generate_to_row())
+            .stored_fields()
+            .map(|f| f.generate_to_row(false))
             .collect::<syn::Result<Vec<_>>>()?;
 
         Ok(quote! {
             impl #impl_generics ::gluesql_derive::ToGlueSqlRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
                 fn to_gluesql_row(&self) -> Vec<::gluesql_derive::gluesql_core::ast_builder::ExprNode<'static>> {
-                    vec![
-                        #(#to_row_fields), *
-                    ]
+                    match self.try_to_gluesql_row() {
+                        Ok(row) => row,
+                        Err(err) => panic!("{}", err),
+                    }
+                }
+                fn try_to_gluesql_row(&self) -> Result<Vec<::gluesql_derive::gluesql_core::ast_builder::ExprNode<'static>>, ::gluesql_derive::Error> {
+                    let mut row = Vec::new();
+                    #(#to_row_fields;)*
+                    Ok(row)
+                }
+            }
+        }
+        .into())
+    }
+    /// Generate the `ToGlueSqlValueRow` implementation.
+    fn generate_to_value_row(mut self) -> syn::Result<TokenStream> {
+        self.validate()?;
+        if self.transparent {
+            return Ok(self.generate_to_gluesql_value()?.into());
+        }
+
+        let ident = &self.ident;
+
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+        let predicates = self.predicates_to(true)?;
+
+        let to_values_fields = self
+            .stored_fields()
+            .map(|f| f.generate_to_row(true))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(quote! {
+            impl #impl_generics ::gluesql_derive::ToGlueSqlValueRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
+                fn to_gluesql_values(&self) -> Vec<::gluesql_derive::gluesql_core::prelude::Value> {
+                    match self.try_to_gluesql_values() {
+                        Ok(row) => row,
+                        Err(err) => panic!("{}", err),
+                    }
+                }
+                fn try_to_gluesql_values(&self) -> Result<Vec<::gluesql_derive::gluesql_core::prelude::Value>, ::gluesql_derive::Error> {
+                    let mut row = Vec::new();
+                    #(#to_values_fields;)*
+                    Ok(row)
                 }
             }
         }
         .into())
     }
-    /// Generates any additional where clause predicates needed for the fields in this struct.
-    pub fn predicates_to(&self) -> syn::Result<Vec<syn::__private::TokenStream2>> {
+    /// Generates any additional where clause predicates needed for the fields in this struct,
+    /// for the `ToGlueSqlValueRow` implementation when `values` is set.
+    pub fn predicates_to(&self, values: bool) -> syn::Result<Vec<syn::__private::TokenStream2>> {
         let mut predicates = Vec::new();
 
-        for field in self.fields() {
-            field.add_predicates_to(&mut predicates)?;
+        for field in self.stored_fields() {
+            field.add_predicates_to(&mut predicates, values)?;
         }
 
         Ok(predicates)
@@ -55,39 +105,88 @@ impl DeriveGluesqlRow {
 impl GluesqlField {
     /// Pushes the needed where clause predicates for this field.
     ///
-    /// By default this is `T: postgres::types::FromSql`,
-    /// when using `flatten` it's: `T: postgres_from_row::FromRow`
-    /// and when using either `from` or `try_from` attributes it additionally pushes this bound:
-    /// `T: std::convert::From<R>`, where `T` is the type specified in the struct and `R` is the
-    /// type specified in the `[try]_from` attribute.
+    /// By default this is `T: gluesql_derive::ToGlueSql`, or `T: gluesql_derive::ToGlueSqlValue` when `values` is set,
+    /// when using `flatten` it's: `T: gluesql_derive::ToGlueSqlRow` (`ToGlueSqlValueRow`),
+    /// when using `timestamp = ".."` it's the trait of the encoding, e.g. `T: gluesql_derive::TimestampMicros`
+    /// and when using either `into` or `try_into` attributes `T` is the type specified in the
+    /// attribute and it additionally pushes this bound: `F: Clone + std::convert::[Try]Into<T>`,
+    /// where `F` is the type specified in the struct.
     pub fn add_predicates_to(
         &self,
         predicates: &mut Vec<syn::__private::TokenStream2>,
+        values: bool,
     ) -> syn::Result<()> {
-        let target_ty = &self.target_ty()?;
-
-        predicates.push(if self.flatten {
-            quote! (#target_ty: ::gluesql_derive::ToGlueSqlRow)
+        let ty = &self.ty;
+        let into_ty = self.write_ty()?;
+
+        predicates.push(if self.flatten && values {
+            quote! (#into_ty: ::gluesql_derive::ToGlueSqlValueRow)
+        } else if self.flatten {
+            quote! (#into_ty: ::gluesql_derive::ToGlueSqlRow)
+        } else if let Some(encoding) = self.timestamp {
+            let trait_path = encoding.trait_path();
+            quote! (#into_ty: #trait_path)
+        } else if values {
+            quote! (#into_ty: ::gluesql_derive::ToGlueSqlValue)
         } else {
-            quote! (#target_ty: ::gluesql_derive::ToGlueSql)
+            quote! (#into_ty: ::gluesql_derive::ToGlueSql)
         });
 
+        if self.into.is_some() {
+            predicates.push(quote!(#ty: Clone + std::convert::Into<#into_ty>));
+        } else if self.try_into.is_some() {
+            let try_into = quote!(std::convert::TryInto<#into_ty>);
+
+            predicates.push(quote!(#ty: Clone + #try_into));
+            predicates.push(quote!(<#ty as #try_into>::Error: std::convert::Into<Box<dyn std::error::Error + Send + Sync>>));
+        }
+
         Ok(())
     }
 
-    /// Generate the line needed to retrieve this field from a row when calling `from_row`.
-    fn generate_to_row(&self) -> syn::Result<TokenStream2> {
-        let ident = self.ident.as_ref().unwrap();
+    /// Generate the statement that pushes this field's values onto the row when calling `to_row`,
+    /// as `ExprNode`s or as `Value`s when `values` is set.
+    fn generate_to_row(&self, values: bool) -> syn::Result<TokenStream2> {
+        let member = self.member();
+        let column_name = self.column_name();
         let field_ty = &self.ty;
+        let into_ty = self.write_ty()?;
 
-        let mut base = if self.flatten {
-            unimplemented!("flatten field not supported")
+        let value = if self.into.is_some() {
+            quote!(&<#field_ty as std::convert::Into<#into_ty>>::into(#member.clone()))
+        } else if self.try_into.is_some() {
+            quote!(&<#field_ty as std::convert::TryInto<#into_ty>>::try_into(#member.clone())
+                .map_err(|e| ::gluesql_derive::Error::ConversionFailed { column: #column_name, source: e.into() })?)
         } else {
-            quote!(&self.#ident)
+            quote!(&#member)
         };
 
-        base = quote!(<#field_ty as ::gluesql_derive::ToGlueSql>::to_gluesql(#base));
-
-        Ok(quote!(#base))
+        if self.flatten && values {
+            Ok(
+                quote!(row.extend(<#into_ty as ::gluesql_derive::ToGlueSqlValueRow>::try_to_gluesql_values(#value)?)),
+            )
+        } else if self.flatten {
+            Ok(
+                quote!(row.extend(<#into_ty as ::gluesql_derive::ToGlueSqlRow>::try_to_gluesql_row(#value)?)),
+            )
+        } else if let Some(encoding) = self.timestamp {
+            let trait_path = encoding.trait_path();
+            let write_fn = if values {
+                encoding.write_value_fn()
+            } else {
+                encoding.write_fn()
+            };
+            if encoding == TimestampEncoding::Offset {
+                Ok(quote!(row.extend(<#into_ty as #trait_path>::#write_fn(#value))))
+            } else {
+                Ok(quote!(row.push(<#into_ty as #trait_path>::#write_fn(#value))))
+            }
+        } else if values {
+            Ok(
+                quote!(row.push(<#into_ty as ::gluesql_derive::ToGlueSqlValue>::try_to_gluesql_value(#value)?)),
+            )
+        } else {
+            Ok(quote!(row.push(<#into_ty as ::gluesql_derive::ToGlueSql>::try_to_gluesql(#value)?)))
+        }
     }
 }
@@ -0,0 +1,112 @@
+use proc_macro2::TokenStream;
+
+use darling::{Error, FromDeriveInput};
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::field::DeriveGluesqlRow;
+
+/// Fallible entry point for generating the value traits of a newtype
+pub fn try_derive_value(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let mut value_derive = DeriveGluesqlRow::from_derive_input(input)?;
+    value_derive.transparent = true;
+    value_derive.validate()?;
+    let from_value = value_derive.generate_from_value()?;
+    let to_gluesql = value_derive.generate_to_gluesql()?;
+    let to_gluesql_value = value_derive.generate_to_gluesql_value()?;
+    let reflect_value = value_derive.generate_reflect_value()?;
+    Ok(quote! {
+        #from_value
+        #to_gluesql
+        #to_gluesql_value
+        #reflect_value
+    })
+}
+
+/// Code generation for transparent structs, which are stored like their single field.
+/// example:
+/// struct UserId(u64);
+/// is read, written and reflected as `u64`.
+impl DeriveGluesqlRow {
+    /// Generate the `FromGlueSql` implementation.
+    pub fn generate_from_value(&self) -> syn::Result<TokenStream> {
+        let ident = &self.ident;
+        let ty = &self.fields()[0].ty;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+        let construct =
+            self.construct(&[quote!(<#ty as ::gluesql_derive::FromGlueSql>::from_gluesql(value)?)]);
+
+        Ok(quote! {
+            impl #impl_generics ::gluesql_derive::FromGlueSql for #ident #ty_generics where #(#original_predicates,)* #ty: ::gluesql_derive::FromGlueSql {
+                fn from_gluesql(value: ::gluesql_derive::gluesql_core::prelude::Value) -> Result<Self, ::gluesql_derive::Error> {
+                    Ok(#construct)
+                }
+            }
+        })
+    }
+    /// Generate the `ToGlueSql` implementation.
+    pub fn generate_to_gluesql(&self) -> syn::Result<TokenStream> {
+        let ident = &self.ident;
+        let field = &self.fields()[0];
+        let ty = &field.ty;
+        let member = field.member();
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+
+        Ok(quote! {
+            impl #impl_generics ::gluesql_derive::ToGlueSql for #ident #ty_generics where #(#original_predicates,)* #ty: ::gluesql_derive::ToGlueSql {
+                fn to_gluesql(&self) -> ::gluesql_derive::gluesql_core::ast_builder::ExprNode<'static> {
+                    <#ty as ::gluesql_derive::ToGlueSql>::to_gluesql(&#member)
+                }
+                fn try_to_gluesql(&self) -> Result<::gluesql_derive::gluesql_core::ast_builder::ExprNode<'static>, ::gluesql_derive::Error> {
+                    <#ty as ::gluesql_derive::ToGlueSql>::try_to_gluesql(&#member)
+                }
+            }
+        })
+    }
+    /// Generate the `ToGlueSqlValue` implementation.
+    pub fn generate_to_gluesql_value(&self) -> syn::Result<TokenStream> {
+        let ident = &self.ident;
+        let field = &self.fields()[0];
+        let ty = &field.ty;
+        let member = field.member();
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+
+        Ok(quote! {
+            impl #impl_generics ::gluesql_derive::ToGlueSqlValue for #ident #ty_generics where #(#original_predicates,)* #ty: ::gluesql_derive::ToGlueSqlValue {
+                fn to_gluesql_value(&self) -> ::gluesql_derive::gluesql_core::prelude::Value {
+                    <#ty as ::gluesql_derive::ToGlueSqlValue>::to_gluesql_value(&#member)
+                }
+                fn try_to_gluesql_value(&self) -> Result<::gluesql_derive::gluesql_core::prelude::Value, ::gluesql_derive::Error> {
+                    <#ty as ::gluesql_derive::ToGlueSqlValue>::try_to_gluesql_value(&#member)
+                }
+            }
+        })
+    }
+    /// Generate the `ReflectGlueSql` implementation.
+    pub fn generate_reflect_value(&self) -> syn::Result<TokenStream> {
+        let ident = &self.ident;
+        let ty = &self.fields()[0].ty;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+
+        Ok(quote! {
+            impl #impl_generics ::gluesql_derive::ReflectGlueSql for #ident #ty_generics where #(#original_predicates,)* #ty: ::gluesql_derive::ReflectGlueSql {
+                fn reflect_gluesql_type() -> String {
+                    <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_type()
+                }
+                fn reflect_gluesql_type_with_nullability() -> String {
+                    <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_type_with_nullability()
+                }
+                fn reflect_gluesql_data_type() -> Result<::gluesql_derive::gluesql_core::ast::DataType, ::gluesql_derive::Error> {
+                    <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_data_type()
+                }
+                fn reflect_gluesql_nullable() -> bool {
+                    <#ty as ::gluesql_derive::ReflectGlueSql>::reflect_gluesql_nullable()
+                }
+            }
+        })
+    }
+}
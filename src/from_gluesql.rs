@@ -2,95 +2,66 @@ use std::any::type_name;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use chrono::{TimeZone, Utc};
+use chrono::{FixedOffset, Local, Utc};
 use gluesql_core::data::{Interval, Value};
 
-use crate::Error;
+use crate::to_gluesql::TIMESTAMP_FORMAT;
+use crate::{Error, Months};
 
 pub trait FromGlueSql: Sized {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error>;
 }
 
-impl FromGlueSql for i8 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::I8(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("i8", value)),
-        }
-    }
-}
-impl FromGlueSql for i16 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::I16(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("i16", value)),
-        }
-    }
-}
-impl FromGlueSql for i32 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::I32(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("i32", value)),
-        }
-    }
-}
-impl FromGlueSql for i64 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::I64(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("i64", value)),
-        }
-    }
-}
-impl FromGlueSql for i128 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::I128(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("i128", value)),
-        }
-    }
+/// Converts any integer `Value` into `T`, failing with `Error::IntegerOverflow` when it does not fit
+fn from_integer<T>(value: Value, target: &'static str) -> Result<T, Error>
+where
+    T: TryFrom<i8>
+        + TryFrom<i16>
+        + TryFrom<i32>
+        + TryFrom<i64>
+        + TryFrom<i128>
+        + TryFrom<u8>
+        + TryFrom<u16>
+        + TryFrom<u32>
+        + TryFrom<u64>
+        + TryFrom<u128>,
+{
+    let converted = match &value {
+        Value::I8(i) => T::try_from(*i).ok(),
+        Value::I16(i) => T::try_from(*i).ok(),
+        Value::I32(i) => T::try_from(*i).ok(),
+        Value::I64(i) => T::try_from(*i).ok(),
+        Value::I128(i) => T::try_from(*i).ok(),
+        Value::U8(i) => T::try_from(*i).ok(),
+        Value::U16(i) => T::try_from(*i).ok(),
+        Value::U32(i) => T::try_from(*i).ok(),
+        Value::U64(i) => T::try_from(*i).ok(),
+        Value::U128(i) => T::try_from(*i).ok(),
+        _ => return Err(Error::InvalidConversion(target, value)),
+    };
+    converted.ok_or(Error::IntegerOverflow(target, value))
 }
-impl FromGlueSql for u8 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::U8(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("u8", value)),
-        }
-    }
-}
-impl FromGlueSql for u16 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::U16(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("u16", value)),
-        }
-    }
-}
-impl FromGlueSql for u32 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::U32(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("u32", value)),
-        }
-    }
-}
-impl FromGlueSql for u64 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::U64(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("u64", value)),
-        }
-    }
+
+/// Parses a timestamp written as text, e.g. an element of a `LIST` or `MAP`
+fn parse_timestamp(s: String, target: &'static str) -> Result<chrono::NaiveDateTime, Error> {
+    chrono::NaiveDateTime::parse_from_str(&s, TIMESTAMP_FORMAT)
+        .map_err(|_e| Error::InvalidConversion(target, Value::Str(s)))
 }
-impl FromGlueSql for u128 {
-    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
-        match value {
-            Value::U128(i) => Ok(i),
-            _ => Err(Error::InvalidConversion("u128", value)),
-        }
-    }
+
+macro_rules! impl_from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl FromGlueSql for $ty {
+                fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+                    from_integer(value, stringify!($ty))
+                }
+            }
+        )*
+    };
 }
+
+impl_from_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
 impl FromGlueSql for f32 {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
@@ -144,6 +115,8 @@ impl FromGlueSql for rust_decimal::Decimal {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
             Value::Decimal(d) => Ok(d),
+            Value::Str(s) => rust_decimal::Decimal::from_str(&s)
+                .map_err(|_e| Error::InvalidConversion("Decimal", Value::Str(s))),
             _ => Err(Error::InvalidConversion("Decimal", value)),
         }
     }
@@ -161,12 +134,8 @@ impl FromGlueSql for chrono::NaiveDate {
 impl FromGlueSql for chrono::NaiveDateTime {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
-            Value::I64(i) => Utc
-                .timestamp_micros(i)
-                .single()
-                .map(|d| d.naive_utc())
-                .ok_or(Error::InvalidConversion("NaiveDateTime", value)),
             Value::Timestamp(d) => Ok(d),
+            Value::Str(s) => parse_timestamp(s, "NaiveDateTime"),
             _ => Err(Error::InvalidConversion("NaiveDateTime", value)),
         }
     }
@@ -174,17 +143,68 @@ impl FromGlueSql for chrono::NaiveDateTime {
 impl FromGlueSql for chrono::DateTime<Utc> {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
-            Value::I64(i) => Utc
-                .timestamp_micros(i)
-                .single()
-                .ok_or(Error::InvalidConversion("DateTime<Utc>", value)),
-
             // There is no Value::TimestampTz
             Value::Timestamp(d) => Ok(d.and_utc()),
+            Value::Str(s) => parse_timestamp(s, "DateTime<Utc>").map(|d| d.and_utc()),
             _ => Err(Error::InvalidConversion("DateTime<Utc>", value)),
         }
     }
 }
+/// Read as UTC, since a `TIMESTAMP` does not store the offset
+impl FromGlueSql for chrono::DateTime<FixedOffset> {
+    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+        match value {
+            Value::Timestamp(d) => Ok(d.and_utc().fixed_offset()),
+            Value::Str(s) => {
+                parse_timestamp(s, "DateTime<FixedOffset>").map(|d| d.and_utc().fixed_offset())
+            }
+            _ => Err(Error::InvalidConversion("DateTime<FixedOffset>", value)),
+        }
+    }
+}
+impl FromGlueSql for chrono::DateTime<Local> {
+    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+        match value {
+            Value::Timestamp(d) => Ok(d.and_utc().with_timezone(&Local)),
+            Value::Str(s) => {
+                parse_timestamp(s, "DateTime<Local>").map(|d| d.and_utc().with_timezone(&Local))
+            }
+            _ => Err(Error::InvalidConversion("DateTime<Local>", value)),
+        }
+    }
+}
+impl FromGlueSql for chrono::NaiveTime {
+    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+        match value {
+            Value::Str(s) => chrono::NaiveTime::from_str(&s)
+                .map_err(|_e| Error::InvalidConversion("NaiveTime", Value::Str(s))),
+            Value::Time(t) => Ok(t),
+            _ => Err(Error::InvalidConversion("NaiveTime", value)),
+        }
+    }
+}
+impl FromGlueSql for std::time::SystemTime {
+    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+        chrono::DateTime::<Utc>::from_gluesql(value).map(Into::into)
+    }
+}
+impl FromGlueSql for Interval {
+    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+        match value {
+            Value::Interval(i) => Ok(i),
+            _ => Err(Error::InvalidConversion("Interval", value)),
+        }
+    }
+}
+impl FromGlueSql for Months {
+    fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
+        match value {
+            Value::Interval(Interval::Month(m)) => Ok(Months(m)),
+            _ => Err(Error::InvalidConversion("Months", value)),
+        }
+    }
+}
+/// Month based intervals have no fixed length, so only microsecond intervals convert into durations
 impl FromGlueSql for chrono::Duration {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
@@ -197,8 +217,7 @@ impl FromGlueSql for chrono::Duration {
 impl FromGlueSql for std::time::Duration {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
-            Value::I64(m) => Ok(std::time::Duration::from_micros(m as u64)),
-            Value::Interval(Interval::Microsecond(m)) => {
+            Value::I64(m) | Value::Interval(Interval::Microsecond(m)) if m >= 0 => {
                 Ok(std::time::Duration::from_micros(m as u64))
             }
             _ => Err(Error::InvalidConversion("std::time::Duration", value)),
@@ -212,6 +231,8 @@ impl FromGlueSql for uuid::Uuid {
                 let uuid = uuid.to_be_bytes();
                 Ok(uuid::Uuid::from_slice(&uuid).unwrap())
             }
+            Value::Str(s) => uuid::Uuid::parse_str(&s)
+                .map_err(|_e| Error::InvalidConversion("Uuid", Value::Str(s))),
             _ => Err(Error::InvalidConversion("Uuid", value)),
         }
     }
@@ -220,6 +241,8 @@ impl FromGlueSql for std::net::IpAddr {
     fn from_gluesql(value: Value) -> gluesql_core::error::Result<Self, Error> {
         match value {
             Value::Inet(ip) => Ok(ip),
+            Value::Str(s) => std::net::IpAddr::from_str(&s)
+                .map_err(|_e| Error::InvalidConversion("IpAddr", Value::Str(s))),
             _ => Err(Error::InvalidConversion("IpAddr", value)),
         }
     }
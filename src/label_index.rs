@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use gluesql_core::data::Value;
+
+use crate::Error;
+
+/// Maps the column labels of a result set to their position in each row.
+/// Built once per batch so that every row is decoded by name without rescanning the labels,
+/// and cheap to clone so that rows can keep it, see `Row`.
+#[derive(Debug, Clone, Default)]
+pub struct LabelIndex {
+    labels: Arc<[String]>,
+    positions: Arc<HashMap<String, usize>>,
+}
+
+impl LabelIndex {
+    pub fn new(labels: &[String]) -> Self {
+        let mut positions = HashMap::with_capacity(labels.len());
+        for (i, label) in labels.iter().enumerate() {
+            positions.entry(label.clone()).or_insert(i);
+        }
+        Self {
+            labels: labels.into(),
+            positions: Arc::new(positions),
+        }
+    }
+    /// The labels in column order
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+    pub fn position(&self, label: &str) -> Option<usize> {
+        self.positions.get(label).copied()
+    }
+    /// Takes the value of column `label` out of `row`, leaving `Value::Null` in its place
+    pub fn take(&self, row: &mut [Value], label: &'static str) -> Result<Value, Error> {
+        let index = self
+            .position(label)
+            .ok_or_else(|| Error::MissingColumn(label.to_string()))?;
+        row.get_mut(index)
+            .map(|value| std::mem::replace(value, Value::Null))
+            .ok_or(Error::InvalidExtract(index, label))
+    }
+    /// Like `take`, but `None` when the column is missing or `NULL`
+    pub fn take_optional(
+        &self,
+        row: &mut [Value],
+        label: &'static str,
+    ) -> Result<Option<Value>, Error> {
+        if self.position(label).is_none() {
+            return Ok(None);
+        }
+        match self.take(row, label)? {
+            Value::Null => Ok(None),
+            value => Ok(Some(value)),
+        }
+    }
+}
@@ -1,28 +1,113 @@
 #![doc = include_str!("../README.md")]
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 #[doc(hidden)]
 pub use gluesql_core;
+use gluesql_core::ast::{ColumnDef, Statement};
 use gluesql_core::ast_builder::ExprNode;
+use gluesql_core::data::Schema;
+use gluesql_core::parse_sql::parse;
 use gluesql_core::prelude::*;
+use gluesql_core::translate::translate;
 
-pub use gluesql_derive_proc::{FromGlueSqlRow, ReflectGlueSqlRow, ToGlueSqlRow};
+pub use gluesql_derive_proc::{
+    FromGlueSqlRow, GlueSqlEnum, GlueSqlValue, ReflectGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+};
 
 mod from_gluesql;
 pub use from_gluesql::FromGlueSql;
 mod to_gluesql;
 pub use to_gluesql::ToGlueSql;
+mod to_gluesql_value;
+pub use to_gluesql_value::ToGlueSqlValue;
 mod reflect_gluesql;
+#[doc(hidden)]
+pub use reflect_gluesql::parse_column_type;
 pub use reflect_gluesql::ReflectGlueSql;
+mod label_index;
+pub use label_index::LabelIndex;
+mod migrate;
+pub use migrate::{diff_table, migrate_table, MigrationMode, SchemaChange};
+mod migrator;
+pub use migrator::{Migration, Migrator, MIGRATIONS_TABLE};
+mod payload;
+pub use payload::PayloadExt;
+mod row;
+pub use row::Row;
+#[doc(hidden)]
+pub use row::{take_map_value, SqlDefault};
+mod storage;
+pub use storage::{insert_rows, scan_rows};
+mod table;
+pub use table::Table;
+mod timestamp;
+pub use timestamp::{TimestampMicros, TimestampOffset, TimestampRfc3339};
+
+/// A month based `INTERVAL`, e.g. `INTERVAL '1-2' YEAR TO MONTH` is `Months(14)`.
+///
+/// Months have no fixed length, so they can't be represented by `chrono::Duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Months(pub i32);
 #[derive(thiserror::Error)]
 pub enum Error {
     #[error("could not convert into type {0}: {1:?}")]
     InvalidConversion(&'static str, Value),
+    #[error("integer {1:?} does not fit into {0}")]
+    IntegerOverflow(&'static str, Value),
+    #[error("could not evaluate the DEFAULT {0:?} without a storage")]
+    UnsupportedDefault(String),
+    #[error("{0} is decoded by position, but the columns of a schemaless row have no order")]
+    UnorderedColumns(&'static str),
+    #[error("the column definitions of {0} are not a single CREATE TABLE column list")]
+    InvalidColumnDefs(&'static str),
+    #[error("{value} can't be written as {target}")]
+    UnsupportedValue { target: &'static str, value: String },
     #[error("could not extract field: {0} {1:?}")]
     InvalidExtract(usize, &'static str),
-    #[error("expected field {0} {1:?}, but actual label is {2:?}")]
-    InvalidFieldName(usize, &'static str, String),
+    #[error("could not convert column {column:?}: {source}")]
+    ConversionFailed {
+        column: &'static str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("table not found: {0:?}")]
+    TableNotFound(String),
+    #[error("missing column: {0:?}")]
+    MissingColumn(String),
+    #[error("{0} has no column marked with #[gluesql(primary_key)]")]
+    MissingPrimaryKey(&'static str),
+    #[error("table {table:?} does not match its description: {changes:?}")]
+    SchemaMismatch {
+        table: String,
+        changes: Vec<SchemaChange>,
+    },
+    #[error("migration {0} is registered more than once")]
+    DuplicateMigration(i64),
+    #[error(
+        "migration {version} was changed after being applied: checksum {expected}, applied {found}"
+    )]
+    MigrationChecksumMismatch {
+        version: i64,
+        expected: String,
+        found: String,
+    },
+    #[error("expected {0} payload, but got {1:?}")]
+    UnexpectedPayload(&'static str, Box<Payload>),
+    #[error("expected a single payload, but got {0}")]
+    UnexpectedPayloadCount(usize),
+    #[error("expected {expected} row, but got {found}")]
+    UnexpectedRowCount {
+        expected: &'static str,
+        found: usize,
+    },
+    #[error(transparent)]
+    GlueSql(Box<gluesql_core::error::Error>),
+}
+impl From<gluesql_core::error::Error> for Error {
+    fn from(error: gluesql_core::error::Error) -> Self {
+        Error::GlueSql(Box::new(error))
+    }
 }
 impl Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -30,19 +115,124 @@ impl Debug for Error {
     }
 }
 pub trait ReflectGlueSqlRow {
-    fn get_ddl(table: &str) -> String;
+    /// column definitions like `id UINT64 NOT NULL`, in the same order as `columns()`
+    fn column_defs() -> Vec<String>;
     fn columns() -> Vec<&'static str>;
+    /// the column marked with `#[gluesql(primary_key)]`, if any
+    fn primary_key() -> Option<&'static str> {
+        None
+    }
+    fn get_ddl(table: &str) -> String {
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} (\n{});",
+            table,
+            Self::column_defs().join(", \n")
+        )
+    }
+    /// `CREATE INDEX` statements for the columns marked with `#[gluesql(index)]`
+    /// and the indexes declared on the struct
+    fn index_ddl(_table: &str) -> Vec<String> {
+        Vec::new()
+    }
+    /// pairs of `(old, new)` column names declared with `#[gluesql(renamed_from = "..")]`
+    fn renamed_columns() -> Vec<(&'static str, &'static str)> {
+        Vec::new()
+    }
+    /// the structured counterpart of `column_defs()`
+    ///
+    /// the default implementation parses `column_defs()`, the derive builds them from the field types instead
+    fn schema_column_defs() -> Result<Vec<ColumnDef>, Error> {
+        let column_defs = Self::column_defs();
+        if column_defs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!("CREATE TABLE t ({})", column_defs.join(", "));
+        let invalid = || Error::InvalidColumnDefs(std::any::type_name::<Self>());
+        let statements = parse(sql).map_err(|_| invalid())?;
+        let [statement] = statements.as_slice() else {
+            return Err(invalid());
+        };
+        match translate(statement)? {
+            Statement::CreateTable {
+                columns: Some(columns),
+                ..
+            } => Ok(columns),
+            _ => Err(invalid()),
+        }
+    }
+    /// the `Schema` of `table`, which can be passed to `StoreMut::insert_schema` instead of running `get_ddl(table)`;
+    /// indexes are not included, see `index_ddl`
+    fn schema(table: &str) -> Result<Schema, Error> {
+        Ok(Schema {
+            table_name: table.to_string(),
+            column_defs: Some(Self::schema_column_defs()?),
+            indexes: Vec::new(),
+            engine: None,
+        })
+    }
+}
+/// Implemented by `#[derive(ReflectGlueSqlRow)]` when the struct declares `#[gluesql(table = "..")]`
+pub trait GlueSqlTable: ReflectGlueSqlRow {
+    const TABLE_NAME: &'static str;
+    fn create_table_ddl() -> String {
+        Self::get_ddl(Self::TABLE_NAME)
+    }
+    fn table_schema() -> Result<Schema, Error> {
+        Self::schema(Self::TABLE_NAME)
+    }
 }
 pub trait FromGlueSqlRow: Sized {
-    fn from_gluesql_row(labels: &[String], row: Vec<Value>) -> Result<Self, Error>;
+    /// decode `Self` by looking up each column by name, ignoring any extra columns
+    fn from_gluesql_row_indexed(labels: &LabelIndex, row: &mut [Value]) -> Result<Self, Error>;
+    fn from_gluesql_row(labels: &[String], mut row: Vec<Value>) -> Result<Self, Error> {
+        Self::from_gluesql_row_indexed(&LabelIndex::new(labels), &mut row)
+    }
     fn from_gluesql_rows(labels: &[String], rows: Vec<Vec<Value>>) -> Result<Vec<Self>, Error> {
+        let labels = LabelIndex::new(labels);
         rows.into_iter()
-            .map(|row| Self::from_gluesql_row(labels, row))
+            .map(|mut row| Self::from_gluesql_row_indexed(&labels, &mut row))
             .collect()
     }
+    /// decode `Self` from a row of a schemaless table, as returned in `Payload::SelectMap`.
+    ///
+    /// The derive takes its columns out of `map`, using the `DEFAULT` of missing columns or `NULL` when the
+    /// field accepts it. The columns of `map` have no order while `from_gluesql_row_indexed` may decode by
+    /// position, so this default implementation only decodes a row with a single column and fails with
+    /// `Error::UnorderedColumns` otherwise: implementations decoding by label should override it.
+    fn from_gluesql_map_entries(map: &mut HashMap<String, Value>) -> Result<Self, Error> {
+        if map.len() != 1 {
+            return Err(Error::UnorderedColumns(std::any::type_name::<Self>()));
+        }
+        let (labels, mut row): (Vec<_>, Vec<_>) = map
+            .iter()
+            .map(|(label, value)| (label.clone(), value.clone()))
+            .unzip();
+        Self::from_gluesql_row_indexed(&LabelIndex::new(&labels), &mut row)
+    }
+    fn from_gluesql_map(mut map: HashMap<String, Value>) -> Result<Self, Error> {
+        Self::from_gluesql_map_entries(&mut map)
+    }
 }
 
 pub trait ToGlueSqlRow {
     /// statically define expression
+    ///
+    /// the derived implementation panics if a `try_into` conversion fails or a value can't be written,
+    /// e.g. an overflowing `Duration`, see `try_to_gluesql_row`
     fn to_gluesql_row(&self) -> Vec<ExprNode<'static>>;
+    fn try_to_gluesql_row(&self) -> Result<Vec<ExprNode<'static>>, Error> {
+        Ok(self.to_gluesql_row())
+    }
+}
+
+/// A row written as typed values, the `Value` counterpart of `ToGlueSqlRow`
+pub trait ToGlueSqlValueRow {
+    /// typed values in the same order as `ToGlueSqlRow::to_gluesql_row`, e.g. to write through `StoreMut`
+    ///
+    /// the derived implementation panics if a `try_into` conversion fails or a value can't be written,
+    /// see `try_to_gluesql_values`
+    fn to_gluesql_values(&self) -> Vec<Value>;
+    fn try_to_gluesql_values(&self) -> Result<Vec<Value>, Error> {
+        Ok(self.to_gluesql_values())
+    }
 }
@@ -0,0 +1,172 @@
+use gluesql_core::ast::{AlterTableOperation, ColumnDef, Statement, ToSql};
+use gluesql_core::data::Schema;
+use gluesql_core::prelude::Glue;
+use gluesql_core::store::{GStore, GStoreMut};
+
+use crate::{Error, ReflectGlueSqlRow};
+
+/// A difference between a stored table and its `ReflectGlueSqlRow` description
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// the table doesn't exist
+    CreateTable(Schema),
+    /// a column of the description is missing from the table
+    AddColumn(ColumnDef),
+    /// a column of the table is not in the description
+    DropColumn(String),
+    /// a column declared with `#[gluesql(renamed_from = "..")]` still has its old name
+    RenameColumn { from: String, to: String },
+    /// a column differs in type, nullability, `DEFAULT` or constraint, which `ALTER TABLE` can't change
+    AlterColumn {
+        stored: ColumnDef,
+        expected: ColumnDef,
+    },
+}
+
+impl SchemaChange {
+    /// the statement applying this change to `table`, `None` for `AlterColumn`
+    pub fn statement(&self, table: &str) -> Option<Statement> {
+        let operation = match self {
+            SchemaChange::CreateTable(schema) => {
+                return Some(Statement::CreateTable {
+                    if_not_exists: false,
+                    name: table.to_string(),
+                    columns: schema.column_defs.clone(),
+                    engine: schema.engine.clone(),
+                    source: None,
+                })
+            }
+            SchemaChange::AddColumn(column_def) => AlterTableOperation::AddColumn {
+                column_def: column_def.clone(),
+            },
+            SchemaChange::DropColumn(column_name) => AlterTableOperation::DropColumn {
+                column_name: column_name.clone(),
+                if_exists: false,
+            },
+            SchemaChange::RenameColumn { from, to } => AlterTableOperation::RenameColumn {
+                old_column_name: from.clone(),
+                new_column_name: to.clone(),
+            },
+            SchemaChange::AlterColumn { .. } => return None,
+        };
+        Some(Statement::AlterTable {
+            name: table.to_string(),
+            operation,
+        })
+    }
+    /// the SQL of `statement(table)`, e.g. `ALTER TABLE "user" ADD COLUMN "age" INT NULL;`
+    pub fn to_sql(&self, table: &str) -> Option<String> {
+        self.statement(table).map(|statement| statement.to_sql())
+    }
+}
+
+/// Whether `migrate_table` changes the table or only checks it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationMode {
+    Apply,
+    VerifyOnly,
+}
+
+/// Compares the stored schema of `table` with the description of `T`.
+///
+/// Renames come first, then dropped, added and altered columns, which is the order `migrate_table` applies them in.
+/// Indexes and column order are not compared, and schemaless tables accept any columns so they have no differences.
+pub async fn diff_table<S, T>(glue: &Glue<S>, table: &str) -> Result<Vec<SchemaChange>, Error>
+where
+    S: GStore + GStoreMut,
+    T: ReflectGlueSqlRow,
+{
+    let expected = T::schema(table)?;
+    let Some(stored) = glue.storage.fetch_schema(table).await? else {
+        return Ok(vec![SchemaChange::CreateTable(expected)]);
+    };
+    let (Some(stored), Some(expected)) = (stored.column_defs, expected.column_defs) else {
+        return Ok(Vec::new());
+    };
+    let renamed = T::renamed_columns();
+
+    let mut renames = Vec::new();
+    let mut adds = Vec::new();
+    let mut alters = Vec::new();
+    let mut matched = Vec::new();
+    for def in &expected {
+        let found = stored.iter().find(|s| s.name == def.name).or_else(|| {
+            let (from, _) = renamed
+                .iter()
+                .find(|(from, to)| *to == def.name && expected.iter().all(|e| e.name != *from))?;
+            let found = stored.iter().find(|s| s.name == *from)?;
+            renames.push(SchemaChange::RenameColumn {
+                from: found.name.clone(),
+                to: def.name.clone(),
+            });
+            Some(found)
+        });
+        let Some(found) = found else {
+            adds.push(SchemaChange::AddColumn(def.clone()));
+            continue;
+        };
+        matched.push(found.name.as_str());
+        let compared = ColumnDef {
+            name: def.name.clone(),
+            ..found.clone()
+        };
+        if compared != *def {
+            alters.push(SchemaChange::AlterColumn {
+                stored: found.clone(),
+                expected: def.clone(),
+            });
+        }
+    }
+    let drops = stored
+        .iter()
+        .filter(|s| !matched.contains(&s.name.as_str()))
+        .map(|s| SchemaChange::DropColumn(s.name.clone()));
+
+    Ok(renames
+        .into_iter()
+        .chain(drops)
+        .chain(adds)
+        .chain(alters)
+        .collect())
+}
+
+/// Brings `table` in line with the description of `T` and returns the changes, as found by `diff_table`.
+///
+/// With `MigrationMode::VerifyOnly`, or when a column would have to be altered, nothing is changed and
+/// any difference is returned as `Error::SchemaMismatch`. A created table also gets the indexes of `T::index_ddl`.
+/// Like `ALTER TABLE`, adding a `NOT NULL` column without `DEFAULT` fails when the table has rows.
+pub async fn migrate_table<S, T>(
+    glue: &mut Glue<S>,
+    table: &str,
+    mode: MigrationMode,
+) -> Result<Vec<SchemaChange>, Error>
+where
+    S: GStore + GStoreMut,
+    T: ReflectGlueSqlRow,
+{
+    let changes = diff_table::<S, T>(glue, table).await?;
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+    let unsupported = changes
+        .iter()
+        .any(|change| matches!(change, SchemaChange::AlterColumn { .. }));
+    if mode == MigrationMode::VerifyOnly || unsupported {
+        return Err(Error::SchemaMismatch {
+            table: table.to_string(),
+            changes,
+        });
+    }
+
+    for change in &changes {
+        if let Some(statement) = change.statement(table) {
+            glue.execute_stmt(&statement).await?;
+        }
+        if let SchemaChange::CreateTable(_) = change {
+            for ddl in T::index_ddl(table) {
+                glue.execute(ddl).await?;
+            }
+        }
+    }
+    Ok(changes)
+}
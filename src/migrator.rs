@@ -0,0 +1,176 @@
+use gluesql_core::ast_builder::{self, Build};
+use gluesql_core::prelude::{Glue, Value};
+use gluesql_core::store::{GStore, GStoreMut};
+
+use crate::{
+    scan_rows, Error, FromGlueSql, FromGlueSqlRow, LabelIndex, ReflectGlueSqlRow, ToGlueSql,
+};
+
+/// The table recording the versions applied by `Migrator`
+pub const MIGRATIONS_TABLE: &str = "_gluesql_migrations";
+
+/// A numbered schema change, run at most once by `Migrator`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    pub version: i64,
+    pub name: String,
+    /// one or more SQL statements separated by `;`
+    pub sql: String,
+    /// Whether `Migrator` rejects an applied migration whose SQL changed since. Off for `create_table`,
+    /// whose SQL is generated from the current struct and so changes with it.
+    pub verify_checksum: bool,
+}
+
+impl Migration {
+    pub fn new(version: i64, name: impl Into<String>, sql: impl Into<String>) -> Self {
+        Self {
+            version,
+            name: name.into(),
+            sql: sql.into(),
+            verify_checksum: true,
+        }
+    }
+    /// Creates `table` and its indexes from the description of `T`, named `create_{table}`.
+    ///
+    /// Once applied, later changes of `T` are not reapplied by this migration: they need their own migrations,
+    /// e.g. generated with `diff_table`.
+    pub fn create_table<T: ReflectGlueSqlRow>(version: i64, table: &str) -> Self {
+        let mut sql = vec![T::get_ddl(table)];
+        sql.extend(T::index_ddl(table));
+        Self {
+            verify_checksum: false,
+            ..Self::new(version, format!("create_{}", table), sql.join("\n"))
+        }
+    }
+    /// A hex encoded 64-bit FNV-1a hash of `sql`, recorded to detect migrations edited after being applied
+    pub fn checksum(&self) -> String {
+        let hash = self.sql.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        format!("{:016x}", hash)
+    }
+}
+
+/// A row of `MIGRATIONS_TABLE`
+struct AppliedMigration {
+    version: i64,
+    checksum: String,
+}
+
+impl FromGlueSqlRow for AppliedMigration {
+    fn from_gluesql_row_indexed(labels: &LabelIndex, row: &mut [Value]) -> Result<Self, Error> {
+        Ok(Self {
+            version: i64::from_gluesql(labels.take(row, "version")?)?,
+            checksum: String::from_gluesql(labels.take(row, "checksum")?)?,
+        })
+    }
+}
+
+/// Runs `Migration`s in version order, recording each applied version in `MIGRATIONS_TABLE`.
+///
+/// Running again only applies the migrations added since, and a migration whose SQL changed after it was applied
+/// is reported as `Error::MigrationChecksumMismatch`, unless it opted out with `verify_checksum`.
+#[derive(Debug, Clone, Default)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn migration(mut self, migration: Migration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+    /// The migrations not applied yet, in version order
+    pub async fn pending<S>(&self, glue: &Glue<S>) -> Result<Vec<&Migration>, Error>
+    where
+        S: GStore + GStoreMut,
+    {
+        let mut migrations = self.migrations.iter().collect::<Vec<_>>();
+        migrations.sort_by_key(|m| m.version);
+        if let Some(pair) = migrations.windows(2).find(|w| w[0].version == w[1].version) {
+            return Err(Error::DuplicateMigration(pair[0].version));
+        }
+
+        let applied = match glue.storage.fetch_schema(MIGRATIONS_TABLE).await? {
+            Some(_) => scan_rows::<_, AppliedMigration>(&glue.storage, MIGRATIONS_TABLE).await?,
+            None => Vec::new(),
+        };
+        let mut pending = Vec::new();
+        for migration in migrations {
+            match applied.iter().find(|a| a.version == migration.version) {
+                Some(applied)
+                    if migration.verify_checksum && applied.checksum != migration.checksum() =>
+                {
+                    return Err(Error::MigrationChecksumMismatch {
+                        version: migration.version,
+                        expected: migration.checksum(),
+                        found: applied.checksum.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => pending.push(migration),
+            }
+        }
+        Ok(pending)
+    }
+    /// Applies the pending migrations and returns their versions.
+    ///
+    /// Each migration and its record run inside a transaction when the storage supports one,
+    /// otherwise a failing migration may be left partially applied, but it is not recorded.
+    pub async fn run<S>(&self, glue: &mut Glue<S>) -> Result<Vec<i64>, Error>
+    where
+        S: GStore + GStoreMut,
+    {
+        let pending = self.pending(glue).await?;
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+        glue.execute(format!(
+            "CREATE TABLE IF NOT EXISTS {} (version INT NOT NULL PRIMARY KEY, name TEXT NOT NULL, checksum TEXT NOT NULL, applied_at TIMESTAMP NOT NULL);",
+            MIGRATIONS_TABLE
+        ))
+        .await?;
+
+        let mut applied = Vec::new();
+        for migration in pending {
+            let transaction = glue.execute("BEGIN;").await.is_ok();
+            match apply(glue, migration).await {
+                Ok(()) if transaction => {
+                    glue.execute("COMMIT;").await?;
+                }
+                Ok(()) => {}
+                Err(error) => {
+                    if transaction {
+                        // report the error of the migration, not a failure to roll it back
+                        let _ = glue.execute("ROLLBACK;").await;
+                    }
+                    return Err(error);
+                }
+            }
+            applied.push(migration.version);
+        }
+        Ok(applied)
+    }
+}
+
+async fn apply<S>(glue: &mut Glue<S>, migration: &Migration) -> Result<(), Error>
+where
+    S: GStore + GStoreMut,
+{
+    glue.execute(&migration.sql).await?;
+    let record = ast_builder::table(MIGRATIONS_TABLE)
+        .insert()
+        .columns("version, name, checksum, applied_at")
+        .values(vec![vec![
+            ast_builder::num(migration.version),
+            ast_builder::text(migration.name.clone()),
+            ast_builder::text(migration.checksum()),
+            chrono::Utc::now().naive_utc().to_gluesql(),
+        ]])
+        .build()?;
+    glue.execute_stmt(&record).await?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::any::type_name;
+
+use gluesql_core::prelude::Payload;
+
+use crate::{Error, FromGlueSql, FromGlueSqlRow};
+
+/// Typed extraction of query results, for `Payload` and the `Vec<Payload>` returned by `Glue::execute`,
+/// which must then hold a single payload.
+///
+/// Rows of `Payload::SelectMap` (schemaless tables) have no column order, so decoding them by position, with a
+/// tuple or `scalar`, fails with `Error::UnorderedColumns`.
+///
+/// ```
+/// # use gluesql_core::prelude::Glue;
+/// # use gluesql_derive::PayloadExt;
+/// # use gluesql_shared_memory_storage::SharedMemoryStorage;
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut glue = Glue::new(SharedMemoryStorage::new());
+/// glue.execute("CREATE TABLE item (id INT, name TEXT)").await?;
+/// assert_eq!(glue.execute("INSERT INTO item VALUES (1, 'a'), (2, 'b')").await?.affected_rows()?, 2);
+/// let count = glue.execute("SELECT COUNT(*) FROM item").await?.scalar::<u32>()?;
+/// assert_eq!(count, 2);
+/// let name = glue.execute("SELECT name FROM item WHERE id = 3").await?.optional_as::<(String,)>()?;
+/// assert_eq!(name, None);
+/// # Ok::<(), gluesql_derive::Error>(())
+/// # }).unwrap();
+/// ```
+pub trait PayloadExt: Sized {
+    /// the result of a `SELECT`
+    fn into_select_payload(self) -> Result<Payload, Error>;
+
+    /// every row of a `SELECT`
+    fn rows_as<T: FromGlueSqlRow>(self) -> Result<Vec<T>, Error> {
+        match self.into_select_payload()? {
+            Payload::Select { labels, rows } => T::from_gluesql_rows(&labels, rows),
+            Payload::SelectMap(rows) => rows.into_iter().map(T::from_gluesql_map).collect(),
+            payload => Err(Error::UnexpectedPayload("Select", Box::new(payload))),
+        }
+    }
+    /// the single row of a `SELECT`, failing with `Error::UnexpectedRowCount` for none or more
+    fn one_as<T: FromGlueSqlRow>(self) -> Result<T, Error> {
+        let mut rows = self.rows_as::<T>()?;
+        match rows.len() {
+            1 => Ok(rows.remove(0)),
+            found => Err(Error::UnexpectedRowCount {
+                expected: "exactly one",
+                found,
+            }),
+        }
+    }
+    /// the row of a `SELECT` returning at most one
+    fn optional_as<T: FromGlueSqlRow>(self) -> Result<Option<T>, Error> {
+        let mut rows = self.rows_as::<T>()?;
+        match rows.len() {
+            0 | 1 => Ok(rows.pop()),
+            found => Err(Error::UnexpectedRowCount {
+                expected: "at most one",
+                found,
+            }),
+        }
+    }
+    /// the first column of the single row of a `SELECT`, e.g. `SELECT COUNT(*) ..`
+    fn scalar<T: FromGlueSql>(self) -> Result<T, Error> {
+        match self.into_select_payload()? {
+            Payload::SelectMap(_) => Err(Error::UnorderedColumns(type_name::<T>())),
+            payload => payload.one_as::<(T,)>().map(|(value,)| value),
+        }
+    }
+    /// the number of rows changed by an `INSERT`, `UPDATE` or `DELETE`
+    fn affected_rows(self) -> Result<usize, Error>;
+}
+
+impl PayloadExt for Payload {
+    fn into_select_payload(self) -> Result<Payload, Error> {
+        match self {
+            Payload::Select { .. } | Payload::SelectMap(_) => Ok(self),
+            payload => Err(Error::UnexpectedPayload("Select", Box::new(payload))),
+        }
+    }
+    fn affected_rows(self) -> Result<usize, Error> {
+        match self {
+            Payload::Insert(n) | Payload::Update(n) | Payload::Delete(n) => Ok(n),
+            payload => Err(Error::UnexpectedPayload(
+                "Insert, Update or Delete",
+                Box::new(payload),
+            )),
+        }
+    }
+}
+
+impl PayloadExt for Vec<Payload> {
+    fn into_select_payload(self) -> Result<Payload, Error> {
+        single(self)?.into_select_payload()
+    }
+    fn affected_rows(self) -> Result<usize, Error> {
+        single(self)?.affected_rows()
+    }
+}
+
+fn single(mut payloads: Vec<Payload>) -> Result<Payload, Error> {
+    match payloads.len() {
+        1 => Ok(payloads.remove(0)),
+        n => Err(Error::UnexpectedPayloadCount(n)),
+    }
+}
@@ -1,6 +1,31 @@
+use gluesql_core::ast::DataType;
+use gluesql_core::parse_sql::parse_data_type;
+use gluesql_core::translate::translate_data_type;
+
+use crate::Error;
+
 pub trait ReflectGlueSql {
     fn reflect_gluesql_type() -> String;
     fn reflect_gluesql_type_with_nullability() -> String;
+    /// the column type as a `DataType`, parsed from `reflect_gluesql_type()` unless overridden
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        parse_column_type(&Self::reflect_gluesql_type()).map(|(data_type, _)| data_type)
+    }
+    /// whether the column accepts `NULL`
+    fn reflect_gluesql_nullable() -> bool {
+        !Self::reflect_gluesql_type_with_nullability().ends_with("NOT NULL")
+    }
+}
+
+/// Parses a column type like `TIMESTAMP NOT NULL` into its `DataType` and nullability.
+#[doc(hidden)]
+pub fn parse_column_type(sql: &str) -> Result<(DataType, bool), Error> {
+    let (data_type, nullable) = match sql.strip_suffix(" NOT NULL") {
+        Some(data_type) => (data_type, false),
+        None => (sql.strip_suffix(" NULL").unwrap_or(sql), true),
+    };
+    let data_type = translate_data_type(&parse_data_type(data_type)?)?;
+    Ok((data_type, nullable))
 }
 // https://gluesql.org/docs/0.15/sql-syntax/data-types/
 // BOOLEAN
@@ -34,6 +59,9 @@ impl ReflectGlueSql for bool {
     fn reflect_gluesql_type_with_nullability() -> String {
         "BOOLEAN NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Boolean)
+    }
 }
 impl ReflectGlueSql for i8 {
     fn reflect_gluesql_type() -> String {
@@ -42,6 +70,9 @@ impl ReflectGlueSql for i8 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INT8 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Int8)
+    }
 }
 impl ReflectGlueSql for i16 {
     fn reflect_gluesql_type() -> String {
@@ -50,6 +81,9 @@ impl ReflectGlueSql for i16 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INT16 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Int16)
+    }
 }
 impl ReflectGlueSql for i32 {
     fn reflect_gluesql_type() -> String {
@@ -58,6 +92,9 @@ impl ReflectGlueSql for i32 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INT32 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Int32)
+    }
 }
 impl ReflectGlueSql for i64 {
     fn reflect_gluesql_type() -> String {
@@ -66,6 +103,9 @@ impl ReflectGlueSql for i64 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INT NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Int)
+    }
 }
 impl ReflectGlueSql for i128 {
     fn reflect_gluesql_type() -> String {
@@ -74,6 +114,9 @@ impl ReflectGlueSql for i128 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INT128 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Int128)
+    }
 }
 impl ReflectGlueSql for u8 {
     fn reflect_gluesql_type() -> String {
@@ -82,6 +125,9 @@ impl ReflectGlueSql for u8 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "UINT8 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Uint8)
+    }
 }
 impl ReflectGlueSql for u16 {
     fn reflect_gluesql_type() -> String {
@@ -90,6 +136,9 @@ impl ReflectGlueSql for u16 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "UINT16 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Uint16)
+    }
 }
 impl ReflectGlueSql for u32 {
     fn reflect_gluesql_type() -> String {
@@ -98,6 +147,9 @@ impl ReflectGlueSql for u32 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "UINT32 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Uint32)
+    }
 }
 impl ReflectGlueSql for u64 {
     fn reflect_gluesql_type() -> String {
@@ -106,6 +158,9 @@ impl ReflectGlueSql for u64 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "UINT64 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Uint64)
+    }
 }
 impl ReflectGlueSql for u128 {
     fn reflect_gluesql_type() -> String {
@@ -114,6 +169,9 @@ impl ReflectGlueSql for u128 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "UINT128 NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Uint128)
+    }
 }
 impl ReflectGlueSql for f32 {
     fn reflect_gluesql_type() -> String {
@@ -122,6 +180,9 @@ impl ReflectGlueSql for f32 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "FLOAT NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Float)
+    }
 }
 impl ReflectGlueSql for f64 {
     fn reflect_gluesql_type() -> String {
@@ -130,6 +191,9 @@ impl ReflectGlueSql for f64 {
     fn reflect_gluesql_type_with_nullability() -> String {
         "FLOAT NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Float)
+    }
 }
 impl ReflectGlueSql for String {
     fn reflect_gluesql_type() -> String {
@@ -138,6 +202,9 @@ impl ReflectGlueSql for String {
     fn reflect_gluesql_type_with_nullability() -> String {
         "TEXT NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Text)
+    }
 }
 impl ReflectGlueSql for &str {
     fn reflect_gluesql_type() -> String {
@@ -146,6 +213,9 @@ impl ReflectGlueSql for &str {
     fn reflect_gluesql_type_with_nullability() -> String {
         "TEXT NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Text)
+    }
 }
 impl ReflectGlueSql for rust_decimal::Decimal {
     fn reflect_gluesql_type() -> String {
@@ -154,6 +224,9 @@ impl ReflectGlueSql for rust_decimal::Decimal {
     fn reflect_gluesql_type_with_nullability() -> String {
         "DECIMAL NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Decimal)
+    }
 }
 impl ReflectGlueSql for chrono::NaiveDate {
     fn reflect_gluesql_type() -> String {
@@ -162,6 +235,9 @@ impl ReflectGlueSql for chrono::NaiveDate {
     fn reflect_gluesql_type_with_nullability() -> String {
         "DATE NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Date)
+    }
 }
 impl ReflectGlueSql for chrono::NaiveDateTime {
     fn reflect_gluesql_type() -> String {
@@ -170,14 +246,20 @@ impl ReflectGlueSql for chrono::NaiveDateTime {
     fn reflect_gluesql_type_with_nullability() -> String {
         "TIMESTAMP NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Timestamp)
+    }
 }
-impl ReflectGlueSql for chrono::DateTime<chrono::Utc> {
+impl<Tz: chrono::TimeZone> ReflectGlueSql for chrono::DateTime<Tz> {
     fn reflect_gluesql_type() -> String {
         "TIMESTAMP".to_string()
     }
     fn reflect_gluesql_type_with_nullability() -> String {
         "TIMESTAMP NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Timestamp)
+    }
 }
 impl ReflectGlueSql for chrono::NaiveTime {
     fn reflect_gluesql_type() -> String {
@@ -186,6 +268,42 @@ impl ReflectGlueSql for chrono::NaiveTime {
     fn reflect_gluesql_type_with_nullability() -> String {
         "TIME NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Time)
+    }
+}
+impl ReflectGlueSql for std::time::SystemTime {
+    fn reflect_gluesql_type() -> String {
+        "TIMESTAMP".to_string()
+    }
+    fn reflect_gluesql_type_with_nullability() -> String {
+        "TIMESTAMP NOT NULL".to_string()
+    }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Timestamp)
+    }
+}
+impl ReflectGlueSql for gluesql_core::data::Interval {
+    fn reflect_gluesql_type() -> String {
+        "INTERVAL".to_string()
+    }
+    fn reflect_gluesql_type_with_nullability() -> String {
+        "INTERVAL NOT NULL".to_string()
+    }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Interval)
+    }
+}
+impl ReflectGlueSql for crate::Months {
+    fn reflect_gluesql_type() -> String {
+        "INTERVAL".to_string()
+    }
+    fn reflect_gluesql_type_with_nullability() -> String {
+        "INTERVAL NOT NULL".to_string()
+    }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Interval)
+    }
 }
 impl ReflectGlueSql for chrono::Duration {
     fn reflect_gluesql_type() -> String {
@@ -194,6 +312,9 @@ impl ReflectGlueSql for chrono::Duration {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INTERVAL NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Interval)
+    }
 }
 impl ReflectGlueSql for std::time::Duration {
     fn reflect_gluesql_type() -> String {
@@ -202,6 +323,9 @@ impl ReflectGlueSql for std::time::Duration {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INTERVAL NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Interval)
+    }
 }
 impl ReflectGlueSql for bytes::Bytes {
     fn reflect_gluesql_type() -> String {
@@ -210,6 +334,9 @@ impl ReflectGlueSql for bytes::Bytes {
     fn reflect_gluesql_type_with_nullability() -> String {
         "BYTEA NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Bytea)
+    }
 }
 impl ReflectGlueSql for std::net::IpAddr {
     fn reflect_gluesql_type() -> String {
@@ -218,6 +345,9 @@ impl ReflectGlueSql for std::net::IpAddr {
     fn reflect_gluesql_type_with_nullability() -> String {
         "INET NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Inet)
+    }
 }
 impl ReflectGlueSql for uuid::Uuid {
     fn reflect_gluesql_type() -> String {
@@ -226,6 +356,9 @@ impl ReflectGlueSql for uuid::Uuid {
     fn reflect_gluesql_type_with_nullability() -> String {
         "UUID NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Uuid)
+    }
 }
 impl<T: ReflectGlueSql> ReflectGlueSql for Option<T> {
     fn reflect_gluesql_type() -> String {
@@ -234,6 +367,12 @@ impl<T: ReflectGlueSql> ReflectGlueSql for Option<T> {
     fn reflect_gluesql_type_with_nullability() -> String {
         format!("{} NULL", T::reflect_gluesql_type())
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        T::reflect_gluesql_data_type()
+    }
+    fn reflect_gluesql_nullable() -> bool {
+        true
+    }
 }
 impl<T: ReflectGlueSql> ReflectGlueSql for Vec<T> {
     fn reflect_gluesql_type() -> String {
@@ -242,6 +381,9 @@ impl<T: ReflectGlueSql> ReflectGlueSql for Vec<T> {
     fn reflect_gluesql_type_with_nullability() -> String {
         "LIST NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::List)
+    }
 }
 impl<K: ReflectGlueSql, V: ReflectGlueSql> ReflectGlueSql for std::collections::HashMap<K, V> {
     fn reflect_gluesql_type() -> String {
@@ -250,4 +392,7 @@ impl<K: ReflectGlueSql, V: ReflectGlueSql> ReflectGlueSql for std::collections::
     fn reflect_gluesql_type_with_nullability() -> String {
         "MAP NOT NULL".to_string()
     }
+    fn reflect_gluesql_data_type() -> Result<DataType, Error> {
+        Ok(DataType::Map)
+    }
 }
@@ -0,0 +1,174 @@
+use std::any::type_name;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use futures::FutureExt;
+use gluesql_core::executor::evaluate_stateless;
+use gluesql_core::parse_sql::parse_expr;
+use gluesql_core::prelude::Value;
+use gluesql_core::translate::translate_expr;
+
+use crate::{Error, FromGlueSql, FromGlueSqlRow, LabelIndex};
+
+/// A row of any result set, for queries that don't deserve a struct.
+///
+/// ```
+/// # use gluesql_core::prelude::{Glue, Payload};
+/// # use gluesql_derive::{FromGlueSqlRow, Row};
+/// # use gluesql_shared_memory_storage::SharedMemoryStorage;
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut glue = Glue::new(SharedMemoryStorage::new());
+/// let Payload::Select { labels, rows } = glue.execute("SELECT 1 AS id, 'alice' AS name").await?.remove(0) else {
+///     unreachable!()
+/// };
+/// let rows = Row::from_gluesql_rows(&labels, rows)?;
+/// assert_eq!(rows[0].get::<u64>("id")?, 1);
+/// assert_eq!(rows[0].get::<String>("name")?, "alice");
+/// # Ok::<(), gluesql_derive::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Row {
+    labels: LabelIndex,
+    values: Vec<Value>,
+}
+
+impl Row {
+    /// Converts the value of `column`
+    pub fn get<T: FromGlueSql>(&self, column: &str) -> Result<T, Error> {
+        let value = self
+            .value(column)
+            .ok_or_else(|| Error::MissingColumn(column.to_string()))?;
+        T::from_gluesql(value.clone())
+    }
+    /// The value of `column`, if the row has it
+    pub fn value(&self, column: &str) -> Option<&Value> {
+        self.values.get(self.labels.position(column)?)
+    }
+    pub fn labels(&self) -> &[String] {
+        self.labels.labels()
+    }
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+}
+
+impl FromGlueSqlRow for Row {
+    fn from_gluesql_row_indexed(labels: &LabelIndex, row: &mut [Value]) -> Result<Self, Error> {
+        Ok(Self {
+            labels: labels.clone(),
+            values: row
+                .iter_mut()
+                .map(|value| std::mem::replace(value, Value::Null))
+                .collect(),
+        })
+    }
+    /// Columns of a schemaless row come in no particular order
+    fn from_gluesql_map_entries(map: &mut HashMap<String, Value>) -> Result<Self, Error> {
+        let (labels, values): (Vec<_>, Vec<_>) = map.drain().unzip();
+        Ok(Self {
+            labels: LabelIndex::new(&labels),
+            values,
+        })
+    }
+}
+
+/// Keyed by label, keeping the first column when labels are repeated
+impl FromGlueSqlRow for HashMap<String, Value> {
+    fn from_gluesql_row_indexed(labels: &LabelIndex, row: &mut [Value]) -> Result<Self, Error> {
+        let mut map = HashMap::with_capacity(row.len());
+        for (i, label) in labels.labels().iter().enumerate() {
+            if labels.position(label) == Some(i) {
+                if let Some(value) = row.get_mut(i) {
+                    map.insert(label.clone(), std::mem::replace(value, Value::Null));
+                }
+            }
+        }
+        Ok(map)
+    }
+    fn from_gluesql_map_entries(map: &mut HashMap<String, Value>) -> Result<Self, Error> {
+        Ok(std::mem::take(map))
+    }
+}
+
+/// The `DEFAULT` expression of a column, evaluated on first use and then reused for every row
+#[doc(hidden)]
+pub struct SqlDefault {
+    sql: &'static str,
+    value: OnceLock<Value>,
+}
+
+impl SqlDefault {
+    pub const fn new(sql: &'static str) -> Self {
+        Self {
+            sql,
+            value: OnceLock::new(),
+        }
+    }
+    pub fn value(&self) -> Result<Value, Error> {
+        if let Some(value) = self.value.get() {
+            return Ok(value.clone());
+        }
+        let expr = translate_expr(&parse_expr(self.sql)?)?;
+        // without a storage nothing awaits, unless the expression needs one, e.g. a subquery
+        let evaluated = evaluate_stateless(None, &expr)
+            .now_or_never()
+            .ok_or_else(|| Error::UnsupportedDefault(self.sql.to_string()))??;
+        let value = Value::try_from(evaluated)?;
+        Ok(self.value.get_or_init(|| value).clone())
+    }
+}
+
+/// Takes the value of `column` out of `map`, or the `DEFAULT` of the column when it's missing
+#[doc(hidden)]
+pub fn take_map_value(
+    map: &mut HashMap<String, Value>,
+    column: &str,
+    default: Option<&SqlDefault>,
+) -> Result<Option<Value>, Error> {
+    match map.remove(column) {
+        Some(value) => Ok(Some(value)),
+        None => default.map(SqlDefault::value).transpose(),
+    }
+}
+
+/// Takes the value at `index` out of `row`, for types decoded by position
+fn take_at<T>(row: &mut [Value], index: usize) -> Result<Value, Error> {
+    row.get_mut(index)
+        .map(|value| std::mem::replace(value, Value::Null))
+        .ok_or(Error::InvalidExtract(index, type_name::<T>()))
+}
+
+/// Tuples are decoded by position, ignoring the labels, so they can't be decoded from a schemaless row
+macro_rules! impl_from_row_tuple {
+    ($($ty:ident $index:tt),+) => {
+        impl<$($ty: FromGlueSql),+> FromGlueSqlRow for ($($ty,)+) {
+            fn from_gluesql_row_indexed(_labels: &LabelIndex, row: &mut [Value]) -> Result<Self, Error> {
+                Ok(($($ty::from_gluesql(take_at::<Self>(row, $index)?)?,)+))
+            }
+            fn from_gluesql_map_entries(_map: &mut HashMap<String, Value>) -> Result<Self, Error> {
+                Err(Error::UnorderedColumns(type_name::<Self>()))
+            }
+        }
+    };
+}
+
+impl_from_row_tuple!(A 0);
+impl_from_row_tuple!(A 0, B 1);
+impl_from_row_tuple!(A 0, B 1, C 2);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14);
+impl_from_row_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15);
@@ -0,0 +1,125 @@
+use futures::TryStreamExt;
+use gluesql_core::ast::{ColumnDef, ColumnUniqueOption};
+use gluesql_core::data::{Key, Schema};
+use gluesql_core::executor::evaluate_stateless;
+use gluesql_core::prelude::Value;
+use gluesql_core::store::{DataRow, Store, StoreMut};
+
+use crate::{Error, FromGlueSqlRow, LabelIndex, ReflectGlueSqlRow, ToGlueSqlValueRow};
+
+/// Writes `rows` into `table` directly through `StoreMut`, bypassing the SQL planner.
+///
+/// Values are laid out and cast following the stored schema, columns not in `T` get their `DEFAULT` or `NULL`,
+/// and rows are keyed by the `PRIMARY KEY` column if any.
+/// Unlike `INSERT`, `UNIQUE` constraints are not checked and a row replaces any stored row with the same primary key.
+pub async fn insert_rows<S, T>(storage: &mut S, table: &str, rows: &[T]) -> Result<usize, Error>
+where
+    S: Store + StoreMut,
+    T: ReflectGlueSqlRow + ToGlueSqlValueRow,
+{
+    let columns = T::columns();
+    let Some(column_defs) = fetch_schema(storage, table).await?.column_defs else {
+        // schemaless table
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let values = row.try_to_gluesql_values()?;
+                Ok(DataRow::Map(
+                    columns.iter().map(|c| c.to_string()).zip(values).collect(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let n = rows.len();
+        storage.append_data(table, rows).await?;
+        return Ok(n);
+    };
+
+    // where each stored column is found in the rows of `T`, or the value to use when it's not
+    let mut sources = Vec::with_capacity(column_defs.len());
+    for def in &column_defs {
+        sources.push(match columns.iter().position(|c| *c == def.name) {
+            Some(i) => Ok(i),
+            None => Err(match &def.default {
+                Some(default) => evaluate_stateless(None, default)
+                    .await?
+                    .try_into_value(&def.data_type, def.nullable)?,
+                None => Value::Null,
+            }),
+        });
+    }
+    let primary_key = column_defs
+        .iter()
+        .position(|def| def.unique == Some(ColumnUniqueOption { is_primary: true }));
+
+    let mut keyed = Vec::new();
+    let mut appended = Vec::new();
+    for row in rows {
+        let mut values = row.try_to_gluesql_values()?;
+        let data = column_defs
+            .iter()
+            .zip(&sources)
+            .map(|(def, source)| {
+                let value = match source {
+                    Ok(i) => std::mem::replace(&mut values[*i], Value::Null),
+                    Err(value) => value.clone(),
+                };
+                conform(value, def)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        match primary_key {
+            Some(i) => keyed.push((Key::try_from(&data[i])?, DataRow::Vec(data))),
+            None => appended.push(DataRow::Vec(data)),
+        }
+    }
+
+    if !keyed.is_empty() {
+        storage.insert_data(table, keyed).await?;
+    }
+    if !appended.is_empty() {
+        storage.append_data(table, appended).await?;
+    }
+    Ok(rows.len())
+}
+
+/// Reads every row of `table` directly through `Store`, bypassing the SQL planner.
+pub async fn scan_rows<S, T>(storage: &S, table: &str) -> Result<Vec<T>, Error>
+where
+    S: Store,
+    T: FromGlueSqlRow,
+{
+    let labels = fetch_schema(storage, table)
+        .await?
+        .column_defs
+        .unwrap_or_default()
+        .into_iter()
+        .map(|def| def.name)
+        .collect::<Vec<_>>();
+    let labels = LabelIndex::new(&labels);
+
+    let mut rows = storage.scan_data(table).await?;
+    let mut result = Vec::new();
+    while let Some((_, row)) = rows.try_next().await? {
+        result.push(match row {
+            DataRow::Vec(mut values) => T::from_gluesql_row_indexed(&labels, &mut values)?,
+            DataRow::Map(map) => T::from_gluesql_map(map)?,
+        });
+    }
+    Ok(result)
+}
+
+async fn fetch_schema<S: Store>(storage: &S, table: &str) -> Result<Schema, Error> {
+    storage
+        .fetch_schema(table)
+        .await?
+        .ok_or_else(|| Error::TableNotFound(table.to_string()))
+}
+
+/// Casts `value` into the type of the column, as `INSERT` does for literals
+fn conform(value: Value, def: &ColumnDef) -> Result<Value, Error> {
+    let value = match value.validate_type(&def.data_type) {
+        Ok(()) => value,
+        Err(_) => value.cast(&def.data_type)?,
+    };
+    value.validate_null(def.nullable)?;
+    Ok(value)
+}
@@ -0,0 +1,159 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+
+use gluesql_core::ast_builder::{self, col, count, Execute, ExprNode};
+use gluesql_core::prelude::{Glue, Payload, Value};
+use gluesql_core::store::{GStore, GStoreMut};
+
+use crate::{Error, FromGlueSqlRow, PayloadExt, ReflectGlueSqlRow, ToGlueSql, ToGlueSqlRow};
+
+/// A typed handle to the table `name`, storing rows of `T`.
+///
+/// ```
+/// # use gluesql_core::prelude::Glue;
+/// # use gluesql_derive::{FromGlueSqlRow, ReflectGlueSqlRow, Table, ToGlueSqlRow};
+/// # use gluesql_shared_memory_storage::SharedMemoryStorage;
+/// #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+/// struct User {
+///     #[gluesql(primary_key)]
+///     id: u64,
+///     name: String,
+/// }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut glue = Glue::new(SharedMemoryStorage::new());
+/// let mut users = Table::<_, User>::new(&mut glue, "users");
+/// users.create().await?;
+/// users.insert(&User { id: 1, name: "alice".to_string() }).await?;
+/// assert_eq!(users.select_all().await?, vec![User { id: 1, name: "alice".to_string() }]);
+/// # Ok::<(), gluesql_derive::Error>(())
+/// # }).unwrap();
+/// ```
+pub struct Table<'a, S: GStore + GStoreMut, T> {
+    glue: &'a mut Glue<S>,
+    name: String,
+    _row: PhantomData<fn() -> T>,
+}
+
+impl<'a, S, T> Table<'a, S, T>
+where
+    S: GStore + GStoreMut,
+    T: ReflectGlueSqlRow + FromGlueSqlRow + ToGlueSqlRow,
+{
+    pub fn new(glue: &'a mut Glue<S>, name: impl Into<String>) -> Self {
+        Self {
+            glue,
+            name: name.into(),
+            _row: PhantomData,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Creates the table if it does not exist, along with its indexes.
+    /// An existing table is left as is, indexes included.
+    pub async fn create(&mut self) -> Result<(), Error> {
+        if self.glue.storage.fetch_schema(&self.name).await?.is_some() {
+            return Ok(());
+        }
+        self.glue.execute(T::get_ddl(&self.name)).await?;
+        for ddl in T::index_ddl(&self.name) {
+            self.glue.execute(ddl).await?;
+        }
+        Ok(())
+    }
+    pub async fn insert(&mut self, row: &T) -> Result<usize, Error> {
+        self.insert_many(std::slice::from_ref(row)).await
+    }
+    pub async fn insert_many(&mut self, rows: &[T]) -> Result<usize, Error> {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let payload = ast_builder::table(&self.name)
+            .insert()
+            .columns(T::columns())
+            .values(
+                rows.iter()
+                    .map(|row| row.try_to_gluesql_row())
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .execute(self.glue)
+            .await?;
+        match payload {
+            Payload::Insert(n) => Ok(n),
+            payload => Err(Error::UnexpectedPayload("Insert", Box::new(payload))),
+        }
+    }
+    pub async fn select_all(&mut self) -> Result<Vec<T>, Error> {
+        let payload = ast_builder::table(&self.name)
+            .select()
+            .project(T::columns())
+            .execute(self.glue)
+            .await?;
+        Self::rows(payload)
+    }
+    pub async fn select_where(&mut self, filter: ExprNode<'_>) -> Result<Vec<T>, Error> {
+        let payload = ast_builder::table(&self.name)
+            .select()
+            .filter(filter)
+            .project(T::columns())
+            .execute(self.glue)
+            .await?;
+        Self::rows(payload)
+    }
+    /// Updates every column of the row whose primary key equals the one of `row`
+    pub async fn update_by_key(&mut self, row: &T) -> Result<usize, Error> {
+        let key = Self::primary_key()?;
+        let mut update = ast_builder::table(&self.name).update();
+        let mut key_value = None;
+        for (column, value) in T::columns().into_iter().zip(row.try_to_gluesql_row()?) {
+            if column == key {
+                key_value = Some(value);
+            } else {
+                update = update.set(column, value);
+            }
+        }
+        let key_value = key_value.ok_or_else(|| Error::MissingColumn(key.to_string()))?;
+        let payload = update
+            .filter(col(key).eq(key_value))
+            .execute(self.glue)
+            .await?;
+        match payload {
+            Payload::Update(n) => Ok(n),
+            payload => Err(Error::UnexpectedPayload("Update", Box::new(payload))),
+        }
+    }
+    pub async fn delete_by_key<K: ToGlueSql>(&mut self, key: &K) -> Result<usize, Error> {
+        let column = Self::primary_key()?;
+        let payload = ast_builder::table(&self.name)
+            .delete()
+            .filter(col(column).eq(key.to_gluesql()))
+            .execute(self.glue)
+            .await?;
+        match payload {
+            Payload::Delete(n) => Ok(n),
+            payload => Err(Error::UnexpectedPayload("Delete", Box::new(payload))),
+        }
+    }
+    pub async fn count(&mut self) -> Result<usize, Error> {
+        let payload = ast_builder::table(&self.name)
+            .select()
+            .project(count("*"))
+            .execute(self.glue)
+            .await?;
+        match payload {
+            Payload::Select { mut rows, .. } => match rows.pop().and_then(|mut row| row.pop()) {
+                Some(Value::I64(n)) => Ok(n as usize),
+                Some(value) => Err(Error::InvalidConversion("usize", value)),
+                None => Ok(0),
+            },
+            payload => Err(Error::UnexpectedPayload("Select", Box::new(payload))),
+        }
+    }
+
+    fn primary_key() -> Result<&'static str, Error> {
+        T::primary_key().ok_or(Error::MissingPrimaryKey(type_name::<T>()))
+    }
+    fn rows(payload: Payload) -> Result<Vec<T>, Error> {
+        payload.rows_as()
+    }
+}
@@ -0,0 +1,231 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use gluesql_core::ast_builder::{null, num, text, ExprNode};
+use gluesql_core::prelude::Value;
+
+use crate::{Error, FromGlueSql, ToGlueSql};
+
+/// Integer epoch encoding of timestamps, used by fields marked with `#[gluesql(timestamp = "micros")]`.
+///
+/// The column is declared as `INT`, like `i64`, and holds the microseconds since the unix epoch in UTC.
+pub trait TimestampMicros: Sized {
+    fn to_gluesql_micros(&self) -> ExprNode<'static>;
+    fn to_gluesql_micros_value(&self) -> Value;
+    fn from_gluesql_micros(value: Value) -> Result<Self, Error>;
+    fn reflect_gluesql_micros_type_with_nullability() -> String {
+        "INT NOT NULL".to_string()
+    }
+}
+
+impl TimestampMicros for NaiveDateTime {
+    fn to_gluesql_micros(&self) -> ExprNode<'static> {
+        num(self.and_utc().timestamp_micros())
+    }
+    fn to_gluesql_micros_value(&self) -> Value {
+        Value::I64(self.and_utc().timestamp_micros())
+    }
+    fn from_gluesql_micros(value: Value) -> Result<Self, Error> {
+        DateTime::<Utc>::from_gluesql_micros(value).map(|d| d.naive_utc())
+    }
+}
+impl TimestampMicros for DateTime<Utc> {
+    fn to_gluesql_micros(&self) -> ExprNode<'static> {
+        num(self.timestamp_micros())
+    }
+    fn to_gluesql_micros_value(&self) -> Value {
+        Value::I64(self.timestamp_micros())
+    }
+    fn from_gluesql_micros(value: Value) -> Result<Self, Error> {
+        match value {
+            Value::I64(i) => Utc
+                .timestamp_micros(i)
+                .single()
+                .ok_or(Error::InvalidConversion("DateTime<Utc>", value)),
+            _ => Err(Error::InvalidConversion("DateTime<Utc>", value)),
+        }
+    }
+}
+impl TimestampMicros for std::time::SystemTime {
+    fn to_gluesql_micros(&self) -> ExprNode<'static> {
+        DateTime::<Utc>::from(*self).to_gluesql_micros()
+    }
+    fn to_gluesql_micros_value(&self) -> Value {
+        DateTime::<Utc>::from(*self).to_gluesql_micros_value()
+    }
+    fn from_gluesql_micros(value: Value) -> Result<Self, Error> {
+        DateTime::<Utc>::from_gluesql_micros(value).map(Into::into)
+    }
+}
+impl<T: TimestampMicros> TimestampMicros for Option<T> {
+    fn to_gluesql_micros(&self) -> ExprNode<'static> {
+        match self {
+            Some(v) => v.to_gluesql_micros(),
+            None => null(),
+        }
+    }
+    fn to_gluesql_micros_value(&self) -> Value {
+        match self {
+            Some(v) => v.to_gluesql_micros_value(),
+            None => Value::Null,
+        }
+    }
+    fn from_gluesql_micros(value: Value) -> Result<Self, Error> {
+        match value {
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::from_gluesql_micros(value)?)),
+        }
+    }
+    fn reflect_gluesql_micros_type_with_nullability() -> String {
+        "INT".to_string()
+    }
+}
+
+/// RFC 3339 text encoding of timestamps, used by fields marked with `#[gluesql(timestamp = "rfc3339")]`.
+///
+/// The column is declared as `TEXT` and keeps the offset of the timestamp, e.g. `2024-01-02T03:04:05+02:00`.
+pub trait TimestampRfc3339: Sized {
+    fn to_gluesql_rfc3339(&self) -> ExprNode<'static>;
+    fn to_gluesql_rfc3339_value(&self) -> Value;
+    fn from_gluesql_rfc3339(value: Value) -> Result<Self, Error>;
+    fn reflect_gluesql_rfc3339_type_with_nullability() -> String {
+        "TEXT NOT NULL".to_string()
+    }
+}
+
+fn parse_rfc3339(value: Value, target: &'static str) -> Result<DateTime<FixedOffset>, Error> {
+    match value {
+        Value::Str(s) => DateTime::parse_from_rfc3339(&s)
+            .map_err(|_e| Error::InvalidConversion(target, Value::Str(s))),
+        _ => Err(Error::InvalidConversion(target, value)),
+    }
+}
+
+impl TimestampRfc3339 for DateTime<FixedOffset> {
+    fn to_gluesql_rfc3339(&self) -> ExprNode<'static> {
+        text(self.to_rfc3339())
+    }
+    fn to_gluesql_rfc3339_value(&self) -> Value {
+        Value::Str(self.to_rfc3339())
+    }
+    fn from_gluesql_rfc3339(value: Value) -> Result<Self, Error> {
+        parse_rfc3339(value, "DateTime<FixedOffset>")
+    }
+}
+impl TimestampRfc3339 for DateTime<Local> {
+    fn to_gluesql_rfc3339(&self) -> ExprNode<'static> {
+        text(self.to_rfc3339())
+    }
+    fn to_gluesql_rfc3339_value(&self) -> Value {
+        Value::Str(self.to_rfc3339())
+    }
+    fn from_gluesql_rfc3339(value: Value) -> Result<Self, Error> {
+        parse_rfc3339(value, "DateTime<Local>").map(|d| d.with_timezone(&Local))
+    }
+}
+impl TimestampRfc3339 for DateTime<Utc> {
+    fn to_gluesql_rfc3339(&self) -> ExprNode<'static> {
+        text(self.to_rfc3339())
+    }
+    fn to_gluesql_rfc3339_value(&self) -> Value {
+        Value::Str(self.to_rfc3339())
+    }
+    fn from_gluesql_rfc3339(value: Value) -> Result<Self, Error> {
+        parse_rfc3339(value, "DateTime<Utc>").map(|d| d.with_timezone(&Utc))
+    }
+}
+impl<T: TimestampRfc3339> TimestampRfc3339 for Option<T> {
+    fn to_gluesql_rfc3339(&self) -> ExprNode<'static> {
+        match self {
+            Some(v) => v.to_gluesql_rfc3339(),
+            None => null(),
+        }
+    }
+    fn to_gluesql_rfc3339_value(&self) -> Value {
+        match self {
+            Some(v) => v.to_gluesql_rfc3339_value(),
+            None => Value::Null,
+        }
+    }
+    fn from_gluesql_rfc3339(value: Value) -> Result<Self, Error> {
+        match value {
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::from_gluesql_rfc3339(value)?)),
+        }
+    }
+    fn reflect_gluesql_rfc3339_type_with_nullability() -> String {
+        "TEXT".to_string()
+    }
+}
+
+/// Two column encoding of timestamps, used by fields marked with `#[gluesql(timestamp = "offset")]`.
+///
+/// The field is expanded into a `TIMESTAMP` column normalised to UTC, which compares like any other timestamp,
+/// and a `{column}_offset` `INT` column holding the offset in seconds east of UTC.
+pub trait TimestampOffset: Sized {
+    fn to_gluesql_offset(&self) -> [ExprNode<'static>; 2];
+    fn to_gluesql_offset_value(&self) -> [Value; 2];
+    fn from_gluesql_offset(timestamp: Value, offset: Value) -> Result<Self, Error>;
+    fn reflect_gluesql_offset_types_with_nullability() -> [String; 2] {
+        ["TIMESTAMP NOT NULL".to_string(), "INT NOT NULL".to_string()]
+    }
+}
+
+impl TimestampOffset for DateTime<FixedOffset> {
+    fn to_gluesql_offset(&self) -> [ExprNode<'static>; 2] {
+        [
+            self.naive_utc().to_gluesql(),
+            num(self.offset().local_minus_utc()),
+        ]
+    }
+    fn to_gluesql_offset_value(&self) -> [Value; 2] {
+        [
+            Value::Timestamp(self.naive_utc()),
+            Value::I64(self.offset().local_minus_utc().into()),
+        ]
+    }
+    fn from_gluesql_offset(timestamp: Value, offset: Value) -> Result<Self, Error> {
+        let timestamp = NaiveDateTime::from_gluesql(timestamp)?;
+        let offset = match offset {
+            Value::I64(seconds) => i32::try_from(seconds)
+                .ok()
+                .and_then(FixedOffset::east_opt)
+                .ok_or(Error::InvalidConversion("FixedOffset", offset))?,
+            _ => return Err(Error::InvalidConversion("FixedOffset", offset)),
+        };
+        Ok(offset.from_utc_datetime(&timestamp))
+    }
+}
+impl TimestampOffset for DateTime<Local> {
+    fn to_gluesql_offset(&self) -> [ExprNode<'static>; 2] {
+        self.fixed_offset().to_gluesql_offset()
+    }
+    fn to_gluesql_offset_value(&self) -> [Value; 2] {
+        self.fixed_offset().to_gluesql_offset_value()
+    }
+    fn from_gluesql_offset(timestamp: Value, offset: Value) -> Result<Self, Error> {
+        DateTime::<FixedOffset>::from_gluesql_offset(timestamp, offset)
+            .map(|d| d.with_timezone(&Local))
+    }
+}
+impl<T: TimestampOffset> TimestampOffset for Option<T> {
+    fn to_gluesql_offset(&self) -> [ExprNode<'static>; 2] {
+        match self {
+            Some(v) => v.to_gluesql_offset(),
+            None => [null(), null()],
+        }
+    }
+    fn to_gluesql_offset_value(&self) -> [Value; 2] {
+        match self {
+            Some(v) => v.to_gluesql_offset_value(),
+            None => [Value::Null, Value::Null],
+        }
+    }
+    fn from_gluesql_offset(timestamp: Value, offset: Value) -> Result<Self, Error> {
+        match (timestamp, offset) {
+            (Value::Null, Value::Null) => Ok(None),
+            (timestamp, offset) => Ok(Some(T::from_gluesql_offset(timestamp, offset)?)),
+        }
+    }
+    fn reflect_gluesql_offset_types_with_nullability() -> [String; 2] {
+        ["TIMESTAMP".to_string(), "INT".to_string()]
+    }
+}
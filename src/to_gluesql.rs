@@ -1,7 +1,22 @@
-use gluesql_core::ast_builder::{date, expr, null, num, text, timestamp, ExprNode};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use gluesql_core::ast::{AstLiteral, DataType, Expr};
+use gluesql_core::ast_builder::{date, expr, null, num, text, time, timestamp, ExprNode};
+use gluesql_core::data::{Interval, Value};
+use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
+
+use crate::{Error, Months, ToGlueSqlValue};
+
+/// The format of `TIMESTAMP` literals
+pub(crate) const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
 
 pub trait ToGlueSql {
     fn to_gluesql(&self) -> ExprNode<'static>;
+    /// like `to_gluesql`, but returns an error for values that can't be written instead of panicking
+    fn try_to_gluesql(&self) -> Result<ExprNode<'static>, Error> {
+        Ok(self.to_gluesql())
+    }
 }
 
 impl ToGlueSql for String {
@@ -44,6 +59,12 @@ impl ToGlueSql for i64 {
         num(*self)
     }
 }
+/// Written through its decimal representation, since `num` has no 128-bit variant
+impl ToGlueSql for i128 {
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        num(self.to_string())
+    }
+}
 impl ToGlueSql for u8 {
     fn to_gluesql(&self) -> ExprNode<'static> {
         num(*self)
@@ -64,6 +85,11 @@ impl ToGlueSql for u64 {
         num(*self)
     }
 }
+impl ToGlueSql for u128 {
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        num(self.to_string())
+    }
+}
 impl ToGlueSql for bool {
     fn to_gluesql(&self) -> ExprNode<'static> {
         (*self).into()
@@ -76,6 +102,12 @@ impl<T: ToGlueSql> ToGlueSql for Option<T> {
             None => null(),
         }
     }
+    fn try_to_gluesql(&self) -> Result<ExprNode<'static>, Error> {
+        match self {
+            Some(v) => v.try_to_gluesql(),
+            None => Ok(null()),
+        }
+    }
 }
 impl ToGlueSql for () {
     fn to_gluesql(&self) -> ExprNode<'static> {
@@ -87,19 +119,21 @@ impl ToGlueSql for rust_decimal::Decimal {
         expr(self.to_string())
     }
 }
-impl ToGlueSql for chrono::DateTime<chrono::Utc> {
+/// Written as a `TIMESTAMP` literal in UTC, the same way `TIMESTAMP '2024-01-01 12:00:00'` is written in SQL.
+/// The offset of the timezone is lost, see `#[gluesql(timestamp = "..")]` to preserve it.
+impl<Tz: chrono::TimeZone> ToGlueSql for chrono::DateTime<Tz> {
     fn to_gluesql(&self) -> ExprNode<'static> {
-        timestamp(self.to_rfc3339())
+        self.naive_utc().to_gluesql()
     }
 }
 impl ToGlueSql for chrono::NaiveDateTime {
     fn to_gluesql(&self) -> ExprNode<'static> {
-        num(self.and_utc().timestamp_micros())
+        timestamp(self.format(TIMESTAMP_FORMAT).to_string())
     }
 }
 impl ToGlueSql for chrono::NaiveTime {
     fn to_gluesql(&self) -> ExprNode<'static> {
-        date(self.to_string())
+        time(self.to_string())
     }
 }
 
@@ -108,22 +142,52 @@ impl ToGlueSql for chrono::NaiveDate {
         date(self.to_string())
     }
 }
+impl ToGlueSql for Interval {
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        expr(format!("INTERVAL {}", self.to_sql_str()))
+    }
+}
+impl ToGlueSql for Months {
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        Interval::Month(self.0).to_gluesql()
+    }
+}
+/// Durations longer than `i64::MAX` microseconds (about 292 000 years) don't fit into an `INTERVAL`
+pub(crate) fn interval_micros(
+    micros: Option<i64>,
+    duration: &dyn std::fmt::Debug,
+) -> Result<Interval, Error> {
+    micros
+        .map(Interval::Microsecond)
+        .ok_or_else(|| Error::UnsupportedValue {
+            target: "a microsecond INTERVAL",
+            value: format!("{:?}", duration),
+        })
+}
 impl ToGlueSql for chrono::Duration {
+    /// Panics if the duration overflows an `INTERVAL`, see `try_to_gluesql`
     fn to_gluesql(&self) -> ExprNode<'static> {
-        num(self.num_microseconds().unwrap())
+        self.try_to_gluesql()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    fn try_to_gluesql(&self) -> Result<ExprNode<'static>, Error> {
+        interval_micros(self.num_microseconds(), self).map(|interval| interval.to_gluesql())
     }
 }
 impl ToGlueSql for std::time::Duration {
+    /// Panics if the duration overflows an `INTERVAL`, see `try_to_gluesql`
     fn to_gluesql(&self) -> ExprNode<'static> {
-        num(self.as_micros() as i64)
+        self.try_to_gluesql()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    fn try_to_gluesql(&self) -> Result<ExprNode<'static>, Error> {
+        interval_micros(i64::try_from(self.as_micros()).ok(), self)
+            .map(|interval| interval.to_gluesql())
     }
 }
 impl ToGlueSql for std::time::SystemTime {
     fn to_gluesql(&self) -> ExprNode<'static> {
-        num(self
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_micros() as i64)
+        chrono::DateTime::<chrono::Utc>::from(*self).to_gluesql()
     }
 }
 
@@ -137,3 +201,89 @@ impl ToGlueSql for std::net::IpAddr {
         text(self.to_string())
     }
 }
+
+impl ToGlueSql for bytes::Bytes {
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        let hex = self.iter().map(|b| format!("{:02x}", b)).collect();
+        ExprNode::Expr(Cow::Owned(Expr::Literal(AstLiteral::HexString(hex))))
+    }
+}
+/// Written as a JSON `LIST` literal, so the elements are read back as `I64`, `F64`, `Bool` or `Str`, which
+/// `FromGlueSql` converts back into the element type. Elements that can't be read back, e.g. an `f32` or an
+/// integer out of the `i64` range, fail `try_to_gluesql`.
+impl<T: ToGlueSqlValue> ToGlueSql for Vec<T> {
+    /// Panics if an element can't be written, see `try_to_gluesql`
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        self.try_to_gluesql()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    fn try_to_gluesql(&self) -> Result<ExprNode<'static>, Error> {
+        Ok(ExprNode::TypedString {
+            data_type: DataType::List,
+            value: to_json(self.try_to_gluesql_value()?)?.to_string().into(),
+        })
+    }
+}
+/// Written as a JSON `MAP` literal, with the same element types as `Vec<T>`
+impl<T: ToGlueSqlValue> ToGlueSql for HashMap<String, T> {
+    /// Panics if an element can't be written, see `try_to_gluesql`
+    fn to_gluesql(&self) -> ExprNode<'static> {
+        self.try_to_gluesql()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    fn try_to_gluesql(&self) -> Result<ExprNode<'static>, Error> {
+        Ok(ExprNode::TypedString {
+            data_type: DataType::Map,
+            value: to_json(self.try_to_gluesql_value()?)?.to_string().into(),
+        })
+    }
+}
+
+/// GlueSQL parses `LIST` and `MAP` literals from JSON text, reading numbers as `I64` or `F64` and strings as `Str`.
+/// Values are encoded so `FromGlueSql` can read them back: decimals, uuids and timestamps as strings,
+/// while values with no such counterpart are rejected instead of being stored differently.
+fn to_json(value: Value) -> Result<JsonValue, Error> {
+    let unsupported = |value: Value| Error::UnsupportedValue {
+        target: "a LIST or MAP element",
+        value: format!("{:?}", value),
+    };
+    let integer = |value: &Value, i: Option<i64>| {
+        i.map(JsonValue::from)
+            .ok_or_else(|| unsupported(value.clone()))
+    };
+    match value {
+        Value::Null => Ok(JsonValue::Null),
+        Value::Bool(b) => Ok(JsonValue::Bool(b)),
+        Value::I8(i) => Ok(i.into()),
+        Value::I16(i) => Ok(i.into()),
+        Value::I32(i) => Ok(i.into()),
+        Value::I64(i) => Ok(i.into()),
+        Value::U8(i) => Ok(i.into()),
+        Value::U16(i) => Ok(i.into()),
+        Value::U32(i) => Ok(i.into()),
+        Value::I128(i) => integer(&value, i64::try_from(i).ok()),
+        Value::U64(i) => integer(&value, i64::try_from(i).ok()),
+        Value::U128(i) => integer(&value, i64::try_from(i).ok()),
+        Value::F64(f) => JsonNumber::from_f64(f)
+            .map(JsonValue::Number)
+            .ok_or_else(|| unsupported(value)),
+        Value::Str(s) => Ok(JsonValue::String(s)),
+        Value::Decimal(d) => Ok(JsonValue::String(d.to_string())),
+        Value::Uuid(u) => Ok(JsonValue::String(uuid::Uuid::from_u128(u).to_string())),
+        Value::Inet(ip) => Ok(JsonValue::String(ip.to_string())),
+        Value::Date(d) => Ok(JsonValue::String(d.to_string())),
+        Value::Time(t) => Ok(JsonValue::String(t.to_string())),
+        Value::Timestamp(t) => Ok(JsonValue::String(t.format(TIMESTAMP_FORMAT).to_string())),
+        Value::List(list) => list
+            .into_iter()
+            .map(to_json)
+            .collect::<Result<_, _>>()
+            .map(JsonValue::Array),
+        Value::Map(map) => map
+            .into_iter()
+            .map(|(k, v)| to_json(v).map(|v| (k, v)))
+            .collect::<Result<JsonMap<_, _>, _>>()
+            .map(JsonValue::Object),
+        value => Err(unsupported(value)),
+    }
+}
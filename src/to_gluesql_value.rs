@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use gluesql_core::data::{Interval, Value};
+
+use crate::to_gluesql::interval_micros;
+use crate::{Error, Months};
+
+/// Converts a type into a typed `Value`, the counterpart of `ToGlueSql` for code that bypasses SQL,
+/// e.g. writing rows through `StoreMut` or comparing rows in tests.
+pub trait ToGlueSqlValue {
+    fn to_gluesql_value(&self) -> Value;
+    /// like `to_gluesql_value`, but returns an error for values that can't be written instead of panicking
+    fn try_to_gluesql_value(&self) -> Result<Value, Error> {
+        Ok(self.to_gluesql_value())
+    }
+}
+
+macro_rules! impl_to_value {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl ToGlueSqlValue for $ty {
+                fn to_gluesql_value(&self) -> Value {
+                    Value::$variant(self.clone())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_value!(
+    bool => Bool,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    f32 => F32,
+    f64 => F64,
+    String => Str,
+    rust_decimal::Decimal => Decimal,
+    std::net::IpAddr => Inet,
+    chrono::NaiveDate => Date,
+    chrono::NaiveDateTime => Timestamp,
+    chrono::NaiveTime => Time,
+    Interval => Interval
+);
+
+impl ToGlueSqlValue for &str {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Str(self.to_string())
+    }
+}
+impl<T: ToGlueSqlValue> ToGlueSqlValue for Option<T> {
+    fn to_gluesql_value(&self) -> Value {
+        match self {
+            Some(v) => v.to_gluesql_value(),
+            None => Value::Null,
+        }
+    }
+    fn try_to_gluesql_value(&self) -> Result<Value, Error> {
+        match self {
+            Some(v) => v.try_to_gluesql_value(),
+            None => Ok(Value::Null),
+        }
+    }
+}
+impl ToGlueSqlValue for () {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Null
+    }
+}
+/// Normalised to UTC, like `ToGlueSql`
+impl<Tz: chrono::TimeZone> ToGlueSqlValue for chrono::DateTime<Tz> {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Timestamp(self.naive_utc())
+    }
+}
+impl ToGlueSqlValue for Months {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Interval(Interval::Month(self.0))
+    }
+}
+impl ToGlueSqlValue for chrono::Duration {
+    /// Panics if the duration overflows an `INTERVAL`, see `try_to_gluesql_value`
+    fn to_gluesql_value(&self) -> Value {
+        self.try_to_gluesql_value()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    fn try_to_gluesql_value(&self) -> Result<Value, Error> {
+        interval_micros(self.num_microseconds(), self).map(Value::Interval)
+    }
+}
+impl ToGlueSqlValue for std::time::Duration {
+    /// Panics if the duration overflows an `INTERVAL`, see `try_to_gluesql_value`
+    fn to_gluesql_value(&self) -> Value {
+        self.try_to_gluesql_value()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    fn try_to_gluesql_value(&self) -> Result<Value, Error> {
+        interval_micros(i64::try_from(self.as_micros()).ok(), self).map(Value::Interval)
+    }
+}
+impl ToGlueSqlValue for std::time::SystemTime {
+    fn to_gluesql_value(&self) -> Value {
+        chrono::DateTime::<chrono::Utc>::from(*self).to_gluesql_value()
+    }
+}
+impl ToGlueSqlValue for uuid::Uuid {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Uuid(self.as_u128())
+    }
+}
+impl ToGlueSqlValue for bytes::Bytes {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Bytea(self.to_vec())
+    }
+}
+impl<T: ToGlueSqlValue> ToGlueSqlValue for Vec<T> {
+    fn to_gluesql_value(&self) -> Value {
+        Value::List(self.iter().map(ToGlueSqlValue::to_gluesql_value).collect())
+    }
+    fn try_to_gluesql_value(&self) -> Result<Value, Error> {
+        self.iter()
+            .map(ToGlueSqlValue::try_to_gluesql_value)
+            .collect::<Result<_, _>>()
+            .map(Value::List)
+    }
+}
+impl<T: ToGlueSqlValue> ToGlueSqlValue for HashMap<String, T> {
+    fn to_gluesql_value(&self) -> Value {
+        Value::Map(
+            self.iter()
+                .map(|(k, v)| (k.clone(), v.to_gluesql_value()))
+                .collect(),
+        )
+    }
+    fn try_to_gluesql_value(&self) -> Result<Value, Error> {
+        self.iter()
+            .map(|(k, v)| v.try_to_gluesql_value().map(|v| (k.clone(), v)))
+            .collect::<Result<_, _>>()
+            .map(Value::Map)
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use gluesql_core::ast::OrderByExpr;
+use gluesql_core::data::{Key, Schema, SchemaIndex, SchemaIndexOrd};
+use gluesql_core::error::{Error, Result};
+use gluesql_core::store::{
+    AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexError, IndexMut, Metadata,
+    RowIter, Store, StoreMut, Transaction,
+};
+use gluesql_memory_storage::MemoryStorage;
+
+/// A `MemoryStorage` that also supports indexes and transactions, which `SharedMemoryStorage` rejects
+#[derive(Default)]
+pub struct MockStorage {
+    pub inner: MemoryStorage,
+    /// the state at `BEGIN`, restored by `ROLLBACK`
+    snapshot: Option<MemoryStorage>,
+    /// makes `ROLLBACK` fail without restoring anything
+    pub fail_rollback: bool,
+}
+
+#[async_trait(?Send)]
+impl Store for MockStorage {
+    async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+        self.inner.fetch_schema(table_name).await
+    }
+    async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
+        self.inner.fetch_all_schemas().await
+    }
+    async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
+        self.inner.fetch_data(table_name, key).await
+    }
+    async fn scan_data(&self, table_name: &str) -> Result<RowIter> {
+        self.inner.scan_data(table_name).await
+    }
+}
+
+#[async_trait(?Send)]
+impl StoreMut for MockStorage {
+    async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+        self.inner.insert_schema(schema).await
+    }
+    async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+        self.inner.delete_schema(table_name).await
+    }
+    async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
+        self.inner.append_data(table_name, rows).await
+    }
+    async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
+        self.inner.insert_data(table_name, rows).await
+    }
+    async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
+        self.inner.delete_data(table_name, keys).await
+    }
+}
+
+/// Only records the index in the schema, nothing is scanned through it
+#[async_trait(?Send)]
+impl IndexMut for MockStorage {
+    async fn create_index(
+        &mut self,
+        table_name: &str,
+        index_name: &str,
+        column: &OrderByExpr,
+    ) -> Result<()> {
+        let item = self
+            .inner
+            .items
+            .get_mut(table_name)
+            .ok_or_else(|| IndexError::TableNotFound(table_name.to_string()))?;
+        let indexes = &mut item.schema.indexes;
+        if indexes.iter().any(|index| index.name == index_name) {
+            return Err(IndexError::IndexNameAlreadyExists(index_name.to_string()).into());
+        }
+        indexes.push(SchemaIndex {
+            name: index_name.to_string(),
+            expr: column.expr.clone(),
+            order: SchemaIndexOrd::Both,
+            created: chrono::Utc::now().naive_utc(),
+        });
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl Transaction for MockStorage {
+    async fn begin(&mut self, autocommit: bool) -> Result<bool> {
+        if autocommit {
+            return Ok(false);
+        }
+        if self.snapshot.is_some() {
+            return Err(Error::StorageMsg("nested transaction".to_string()));
+        }
+        self.snapshot = Some(self.inner.clone());
+        Ok(false)
+    }
+    async fn rollback(&mut self) -> Result<()> {
+        let snapshot = self.snapshot.take();
+        if self.fail_rollback {
+            return Err(Error::StorageMsg("rollback failed".to_string()));
+        }
+        if let Some(snapshot) = snapshot {
+            self.inner = snapshot;
+        }
+        Ok(())
+    }
+    async fn commit(&mut self) -> Result<()> {
+        self.snapshot = None;
+        Ok(())
+    }
+}
+
+impl Index for MockStorage {}
+impl AlterTable for MockStorage {}
+impl Metadata for MockStorage {}
+impl CustomFunction for MockStorage {}
+impl CustomFunctionMut for MockStorage {}
@@ -0,0 +1,68 @@
+use gluesql_core::data::Value;
+use gluesql_derive::{FromGlueSql, GlueSqlEnum, ReflectGlueSql, ReflectGlueSqlRow};
+
+#[test]
+fn test_enum_text() {
+    #[derive(Debug, PartialEq, GlueSqlEnum)]
+    #[gluesql(rename_all = "snake_case")]
+    enum Status {
+        InProgress,
+        #[gluesql(rename = "finished")]
+        Done,
+    }
+    assert_eq!(Status::reflect_gluesql_type(), "TEXT");
+    assert_eq!(
+        Status::from_gluesql(Value::Str("in_progress".to_string())).unwrap(),
+        Status::InProgress
+    );
+    assert_eq!(
+        Status::from_gluesql(Value::Str("finished".to_string())).unwrap(),
+        Status::Done
+    );
+    assert!(Status::from_gluesql(Value::Str("Done".to_string())).is_err());
+}
+
+#[test]
+fn test_enum_pascal_case() {
+    #[derive(Debug, PartialEq, GlueSqlEnum)]
+    #[gluesql(rename_all = "PascalCase")]
+    #[allow(non_camel_case_types)]
+    enum Status {
+        in_progress,
+    }
+    assert_eq!(
+        Status::from_gluesql(Value::Str("InProgress".to_string())).unwrap(),
+        Status::in_progress
+    );
+}
+
+#[test]
+fn test_enum_int() {
+    #[derive(Debug, PartialEq, GlueSqlEnum)]
+    #[gluesql(repr = "int")]
+    enum Priority {
+        Low = 1,
+        High = 10,
+    }
+    assert_eq!(Priority::reflect_gluesql_type(), "INT");
+    assert_eq!(
+        Priority::reflect_gluesql_data_type().unwrap(),
+        gluesql_core::ast::DataType::Int
+    );
+    assert_eq!(
+        Priority::from_gluesql(Value::I64(10)).unwrap(),
+        Priority::High
+    );
+    assert!(Priority::from_gluesql(Value::I64(2)).is_err());
+
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct Task {
+        priority: Priority,
+        next: Option<Priority>,
+    }
+    assert_eq!(
+        Task::get_ddl("task"),
+        "CREATE TABLE IF NOT EXISTS task (\npriority INT NOT NULL, \nnext INT NULL);"
+    );
+}
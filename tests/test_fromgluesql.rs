@@ -1,5 +1,5 @@
 use gluesql_core::data::Value;
-use gluesql_derive::FromGlueSqlRow;
+use gluesql_derive::{Error, FromGlueSqlRow, ReflectGlueSqlRow};
 
 #[test]
 fn test_fromgluesql_field_struct() {
@@ -26,7 +26,7 @@ fn test_fromgluesql_field_struct() {
     )
     .unwrap();
     assert_eq!(data.a, 1);
-    assert_eq!(data.b, true);
+    assert!(data.b);
     assert_eq!(data.c, "hello");
     assert_eq!(data.d, None);
 }
@@ -53,3 +53,419 @@ fn test_fromgluesql_field_struct_field_mismatch() {
     )
     .unwrap();
 }
+
+#[test]
+fn test_fromgluesql_flatten() {
+    #[derive(FromGlueSqlRow)]
+    struct Audit {
+        created_at: i64,
+        updated_at: Option<i64>,
+    }
+    #[derive(FromGlueSqlRow)]
+    struct Foo {
+        id: i64,
+        #[gluesql(flatten)]
+        audit: Audit,
+        name: String,
+    }
+    let data = Foo::from_gluesql_row(
+        &[
+            "id".to_string(),
+            "created_at".to_string(),
+            "updated_at".to_string(),
+            "name".to_string(),
+        ],
+        vec![
+            Value::I64(1),
+            Value::I64(2),
+            Value::Null,
+            Value::Str("hello".to_string()),
+        ],
+    )
+    .unwrap();
+    assert_eq!(data.id, 1);
+    assert_eq!(data.audit.created_at, 2);
+    assert_eq!(data.audit.updated_at, None);
+    assert_eq!(data.name, "hello");
+}
+
+#[test]
+fn test_fromgluesql_by_label() {
+    #[derive(FromGlueSqlRow)]
+    struct Foo {
+        a: i64,
+        b: bool,
+    }
+    let labels = ["extra".to_string(), "b".to_string(), "a".to_string()];
+    let data = Foo::from_gluesql_rows(
+        &labels,
+        vec![
+            vec![Value::Null, Value::Bool(true), Value::I64(1)],
+            vec![Value::I64(9), Value::Bool(false), Value::I64(2)],
+        ],
+    )
+    .unwrap();
+    assert_eq!(data[0].a, 1);
+    assert!(data[0].b);
+    assert_eq!(data[1].a, 2);
+    assert!(!data[1].b);
+}
+
+#[test]
+fn test_fromgluesql_missing_column() {
+    #[derive(Debug, FromGlueSqlRow)]
+    #[allow(unused)]
+    struct Foo {
+        a: i64,
+        b: bool,
+    }
+    let err = Foo::from_gluesql_row(&["a".to_string()], vec![Value::I64(1)]).unwrap_err();
+    assert!(matches!(err, Error::MissingColumn(column) if column == "b"));
+}
+
+#[test]
+fn test_fromgluesql_attributes() {
+    #[derive(Debug, PartialEq)]
+    struct UserId(i64);
+    impl From<i64> for UserId {
+        fn from(id: i64) -> Self {
+            UserId(id)
+        }
+    }
+    #[derive(FromGlueSqlRow, ReflectGlueSqlRow)]
+    struct Foo {
+        #[gluesql(rename = "user_id", from = "i64")]
+        id: UserId,
+    }
+    assert_eq!(Foo::columns(), vec!["user_id"]);
+    assert_eq!(
+        Foo::get_ddl("foo"),
+        "CREATE TABLE IF NOT EXISTS foo (\nuser_id INT NOT NULL);"
+    );
+    let data = Foo::from_gluesql_row(&["user_id".to_string()], vec![Value::I64(7)]).unwrap();
+    assert_eq!(data.id, UserId(7));
+}
+
+#[test]
+fn test_fromgluesql_try_from_error() {
+    #[derive(Debug)]
+    #[allow(unused)]
+    struct Port(u16);
+    impl TryFrom<i64> for Port {
+        type Error = std::num::TryFromIntError;
+        fn try_from(port: i64) -> Result<Self, Self::Error> {
+            u16::try_from(port).map(Port)
+        }
+    }
+    #[derive(Debug, FromGlueSqlRow)]
+    #[allow(unused)]
+    struct Foo {
+        #[gluesql(try_from = "i64")]
+        port: Port,
+    }
+    let err = Foo::from_gluesql_row(&["port".to_string()], vec![Value::I64(-1)]).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ConversionFailed { column: "port", .. }
+    ));
+}
+
+#[test]
+fn test_fromgluesql_integer_widening() {
+    use gluesql_derive::FromGlueSql;
+
+    assert_eq!(i32::from_gluesql(Value::I64(-7)).unwrap(), -7);
+    assert_eq!(u8::from_gluesql(Value::I128(255)).unwrap(), 255);
+    assert_eq!(
+        i128::from_gluesql(Value::U64(u64::MAX)).unwrap(),
+        u64::MAX as i128
+    );
+    assert!(matches!(
+        i32::from_gluesql(Value::I64(i64::MAX)),
+        Err(Error::IntegerOverflow("i32", Value::I64(i64::MAX)))
+    ));
+    assert!(matches!(
+        u64::from_gluesql(Value::I8(-1)),
+        Err(Error::IntegerOverflow("u64", Value::I8(-1)))
+    ));
+    assert!(matches!(
+        i64::from_gluesql(Value::F64(1.0)),
+        Err(Error::InvalidConversion("i64", _))
+    ));
+}
+
+#[tokio::test]
+async fn test_fromgluesql_adhoc() -> eyre::Result<()> {
+    use gluesql_core::prelude::{Glue, Payload};
+    use gluesql_derive::Row;
+    use gluesql_shared_memory_storage::SharedMemoryStorage;
+    use std::collections::HashMap;
+
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE item (id INT, kind TEXT)")
+        .await?;
+    glue.execute("INSERT INTO item VALUES (1, 'a'), (2, 'a'), (3, 'b')")
+        .await?;
+    let Payload::Select { labels, rows } = glue
+        .execute("SELECT kind, COUNT(*) AS n FROM item GROUP BY kind ORDER BY kind")
+        .await?
+        .remove(0)
+    else {
+        panic!("unexpected result");
+    };
+
+    assert_eq!(
+        <(String, i64)>::from_gluesql_rows(&labels, rows.clone())?,
+        vec![("a".to_string(), 2), ("b".to_string(), 1)]
+    );
+    assert_eq!(
+        <(String,)>::from_gluesql_rows(&labels, rows.clone())?,
+        vec![("a".to_string(),), ("b".to_string(),)]
+    );
+    assert!(matches!(
+        <(String, i64, i64)>::from_gluesql_rows(&labels, rows.clone()),
+        Err(Error::InvalidExtract(2, _))
+    ));
+
+    let maps = HashMap::<String, Value>::from_gluesql_rows(&labels, rows.clone())?;
+    assert_eq!(maps[1]["kind"], Value::Str("b".to_string()));
+    assert_eq!(maps[1]["n"], Value::I64(1));
+
+    let rows = Row::from_gluesql_rows(&labels, rows)?;
+    assert_eq!(rows[0].labels(), ["kind", "n"]);
+    assert_eq!(rows[0].get::<String>("kind")?, "a");
+    assert_eq!(rows[0].get::<u8>("n")?, 2);
+    assert_eq!(rows[0].value("n"), Some(&Value::I64(2)));
+    // column names only known at runtime
+    let column = format!("{}{}", "ki", "nd");
+    assert_eq!(rows[0].get::<String>(&column)?, "a");
+    let labels = rows[0].labels().to_vec();
+    assert_eq!(rows[0].get::<i64>(&labels[1])?, 2);
+    assert!(matches!(
+        rows[0].get::<i64>("missing"),
+        Err(Error::MissingColumn(column)) if column == "missing"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_fromgluesql_map() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, FromGlueSqlRow)]
+    struct Audit {
+        #[gluesql(timestamp = "offset")]
+        updated_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    }
+    #[derive(Debug, PartialEq, FromGlueSqlRow)]
+    struct Doc {
+        id: u64,
+        #[gluesql(rename = "title")]
+        name: String,
+        #[gluesql(default = "'draft'")]
+        status: String,
+        #[gluesql(default = "1 + 2")]
+        version: i32,
+        note: Option<String>,
+        #[gluesql(flatten)]
+        audit: Audit,
+    }
+
+    let map = HashMap::from([
+        ("id".to_string(), Value::I64(1)),
+        ("title".to_string(), Value::Str("hello".to_string())),
+        ("extra".to_string(), Value::Bool(true)),
+    ]);
+    assert_eq!(
+        Doc::from_gluesql_map(map).unwrap(),
+        Doc {
+            id: 1,
+            name: "hello".to_string(),
+            status: "draft".to_string(),
+            version: 3,
+            note: None,
+            audit: Audit { updated_at: None },
+        }
+    );
+
+    let map = HashMap::from([
+        ("id".to_string(), Value::I64(1)),
+        ("title".to_string(), Value::Str("hello".to_string())),
+        ("status".to_string(), Value::Str("published".to_string())),
+        ("note".to_string(), Value::Str("n".to_string())),
+    ]);
+    let doc = Doc::from_gluesql_map(map).unwrap();
+    assert_eq!(doc.status, "published");
+    assert_eq!(doc.note.as_deref(), Some("n"));
+
+    let map = HashMap::from([("id".to_string(), Value::I64(1))]);
+    assert!(matches!(
+        Doc::from_gluesql_map(map),
+        Err(Error::MissingColumn(column)) if column == "title"
+    ));
+}
+
+#[test]
+fn test_fromgluesql_map_fallback() {
+    use std::collections::HashMap;
+
+    fn one() -> i64 {
+        1
+    }
+    #[derive(Debug, PartialEq, FromGlueSqlRow)]
+    struct Counter {
+        #[gluesql(default)]
+        name: String,
+        #[gluesql(default_with = "one")]
+        step: i64,
+        #[gluesql(skip)]
+        cached: Option<i64>,
+    }
+
+    let map = HashMap::from([
+        ("step".to_string(), Value::Null),
+        ("cached".to_string(), Value::I64(5)),
+    ]);
+    assert_eq!(
+        Counter::from_gluesql_map(map).unwrap(),
+        Counter {
+            name: String::new(),
+            step: 1,
+            cached: None,
+        }
+    );
+
+    let map = HashMap::from([
+        ("name".to_string(), Value::Str("hits".to_string())),
+        ("step".to_string(), Value::I64(2)),
+    ]);
+    assert_eq!(
+        Counter::from_gluesql_map(map).unwrap(),
+        Counter {
+            name: "hits".to_string(),
+            step: 2,
+            cached: None,
+        }
+    );
+}
+
+#[test]
+fn test_fromgluesql_sql_default() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, FromGlueSqlRow)]
+    struct Doc {
+        id: i64,
+        #[gluesql(default = "'draft'")]
+        status: String,
+        #[gluesql(default = "1 + 2")]
+        version: Option<i32>,
+    }
+
+    // a key missing from a schemaless row was never set, so it gets its DEFAULT
+    for _ in 0..2 {
+        let map = HashMap::from([("id".to_string(), Value::I64(1))]);
+        assert_eq!(
+            Doc::from_gluesql_map(map).unwrap(),
+            Doc {
+                id: 1,
+                status: "draft".to_string(),
+                version: Some(3),
+            }
+        );
+    }
+    // a column left out of a result set says nothing about its stored value
+    let labels = ["id".to_string(), "version".to_string()];
+    assert!(matches!(
+        Doc::from_gluesql_row(&labels, vec![Value::I64(1), Value::Null]),
+        Err(Error::MissingColumn(column)) if column == "status"
+    ));
+    assert!(matches!(
+        Doc::from_gluesql_row(&labels[1..], vec![Value::Null]),
+        Err(Error::MissingColumn(column)) if column == "id"
+    ));
+
+    #[derive(Debug, FromGlueSqlRow)]
+    struct Broken {
+        #[allow(unused)]
+        #[gluesql(default = "1 +")]
+        n: i64,
+    }
+    assert!(Broken::from_gluesql_row(&[], vec![]).is_err());
+    assert!(Broken::from_gluesql_map(HashMap::new()).is_err());
+}
+
+#[test]
+fn test_fromgluesql_map_positional() {
+    use gluesql_derive::{FromGlueSql, LabelIndex, Row};
+    use std::collections::HashMap;
+
+    /// decodes its single column by position
+    struct Count(i64);
+    impl FromGlueSqlRow for Count {
+        fn from_gluesql_row_indexed(
+            _labels: &LabelIndex,
+            row: &mut [Value],
+        ) -> Result<Self, Error> {
+            Ok(Count(i64::from_gluesql(row[0].clone())?))
+        }
+    }
+
+    let map = HashMap::from([
+        ("id".to_string(), Value::I64(1)),
+        ("name".to_string(), Value::Str("a".to_string())),
+    ]);
+    assert!(matches!(
+        <(i64, String)>::from_gluesql_map(map.clone()),
+        Err(Error::UnorderedColumns(_))
+    ));
+    assert!(matches!(
+        Count::from_gluesql_map(map.clone()),
+        Err(Error::UnorderedColumns(_))
+    ));
+    let single = HashMap::from([("n".to_string(), Value::I64(3))]);
+    assert!(matches!(
+        <(i64,)>::from_gluesql_map(single.clone()),
+        Err(Error::UnorderedColumns(_))
+    ));
+    assert_eq!(Count::from_gluesql_map(single).unwrap().0, 3);
+
+    // decoded by label
+    let row = Row::from_gluesql_map(map.clone()).unwrap();
+    assert_eq!(row.get::<String>("name").unwrap(), "a");
+    assert_eq!(row.get::<i64>("id").unwrap(), 1);
+    assert_eq!(
+        HashMap::<String, Value>::from_gluesql_map(map.clone()).unwrap(),
+        map
+    );
+}
+
+#[tokio::test]
+async fn test_fromgluesql_map_schemaless() -> eyre::Result<()> {
+    use gluesql_core::prelude::Glue;
+    use gluesql_derive::PayloadExt;
+    use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+    #[derive(Debug, PartialEq, FromGlueSqlRow)]
+    struct Doc {
+        id: i64,
+        tags: Option<Vec<String>>,
+    }
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE doc").await?;
+    glue.execute(r#"INSERT INTO doc VALUES ('{"id": 1, "tags": ["a"]}'), ('{"id": 2}')"#)
+        .await?;
+    let mut docs = glue.execute("SELECT * FROM doc").await?.rows_as::<Doc>()?;
+    docs.sort_by_key(|d| d.id);
+    assert_eq!(
+        docs,
+        vec![
+            Doc {
+                id: 1,
+                tags: Some(vec!["a".to_string()])
+            },
+            Doc { id: 2, tags: None },
+        ]
+    );
+    Ok(())
+}
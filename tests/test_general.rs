@@ -1,18 +1,26 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
+use gluesql_core::ast::Statement;
 use gluesql_core::ast_builder;
 use gluesql_core::ast_builder::Build;
+use gluesql_core::parse_sql::parse;
 use gluesql_core::prelude::{Glue, Payload};
+use gluesql_core::translate::translate;
 use gluesql_shared_memory_storage::SharedMemoryStorage;
 
 use gluesql_derive::{
-    FromGlueSql, FromGlueSqlRow, ReflectGlueSql, ReflectGlueSqlRow, ToGlueSql, ToGlueSqlRow,
+    FromGlueSql, FromGlueSqlRow, GlueSqlEnum, GlueSqlValue, ReflectGlueSql, ReflectGlueSqlRow,
+    TimestampMicros, ToGlueSql, ToGlueSqlRow, ToGlueSqlValue, ToGlueSqlValueRow,
 };
 
 async fn test_type<T>(val: T) -> eyre::Result<()>
 where
-    T: PartialEq + std::fmt::Debug + ReflectGlueSql + FromGlueSql + ToGlueSql,
+    T: PartialEq + std::fmt::Debug + ReflectGlueSql + FromGlueSql + ToGlueSql + ToGlueSqlValue,
 {
-    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
     struct Foo<T> {
         foo: T,
     }
@@ -20,11 +28,11 @@ where
     let mut glue = Glue::new(db);
     let ddl = Foo::<T>::get_ddl("foo");
     glue.execute(ddl.as_str()).await?;
-    let foo = Foo::<T> { foo: val };
+    let data = Foo::<T> { foo: val };
     let insert = ast_builder::table("foo")
         .insert()
         .columns(Foo::<T>::columns())
-        .values(vec![foo.to_gluesql_row()])
+        .values(vec![data.to_gluesql_row()])
         .build()?;
     glue.execute_stmt(&insert).await?;
     let select = ast_builder::table("foo")
@@ -35,8 +43,9 @@ where
     let result = glue.execute_stmt(&select).await?;
     match result {
         Payload::Select { labels, rows } => {
+            assert_eq!(rows[0], data.to_gluesql_values());
             let row = Foo::from_gluesql_rows(&labels, rows)?;
-            assert_eq!(row[0], foo);
+            assert_eq!(row[0], data);
         }
         _ => panic!("unexpected result"),
     }
@@ -59,5 +68,649 @@ async fn test_duration() -> eyre::Result<()> {
 }
 #[tokio::test]
 async fn test_std_duration() -> eyre::Result<()> {
-    test_type(std::time::Duration::from_secs(1)).await
+    test_type(std::time::Duration::from_secs(1)).await?;
+    test_type(std::time::Duration::from_micros(1_500_000)).await
+}
+
+#[tokio::test]
+async fn test_naive_time() -> eyre::Result<()> {
+    test_type(chrono::NaiveTime::from_hms_micro_opt(12, 34, 56, 789).unwrap()).await
+}
+
+#[tokio::test]
+async fn test_system_time() -> eyre::Result<()> {
+    test_type(std::time::UNIX_EPOCH + std::time::Duration::from_micros(1_700_000_000_123_456)).await
+}
+
+#[tokio::test]
+async fn test_interval() -> eyre::Result<()> {
+    test_type(chrono::Duration::milliseconds(-1500)).await?;
+    test_type(gluesql_derive::Months(14)).await?;
+    test_type(gluesql_core::data::Interval::Month(-3)).await?;
+    test_type(gluesql_core::data::Interval::Microsecond(42)).await
+}
+
+#[tokio::test]
+async fn test_int128() -> eyre::Result<()> {
+    test_type(i128::MIN).await?;
+    test_type(i128::MAX).await?;
+    test_type(u128::MAX).await
+}
+
+#[tokio::test]
+async fn test_integer_widening() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, FromGlueSqlRow)]
+    struct Small {
+        id: i32,
+        count: u8,
+    }
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute("CREATE TABLE small (id INT, count INT128)")
+        .await?;
+    glue.execute("INSERT INTO small VALUES (1, 2)").await?;
+    match glue.execute("SELECT * FROM small").await?.remove(0) {
+        Payload::Select { labels, rows } => {
+            assert_eq!(
+                Small::from_gluesql_rows(&labels, rows)?,
+                vec![Small { id: 1, count: 2 }]
+            );
+        }
+        _ => panic!("unexpected result"),
+    }
+    glue.execute("INSERT INTO small VALUES (3, 256)").await?;
+    match glue
+        .execute("SELECT * FROM small WHERE id = 3")
+        .await?
+        .remove(0)
+    {
+        Payload::Select { labels, rows } => {
+            assert!(matches!(
+                Small::from_gluesql_rows(&labels, rows),
+                Err(gluesql_derive::Error::IntegerOverflow("u8", _))
+            ));
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list() -> eyre::Result<()> {
+    test_type(vec![1i64, -2, 3]).await?;
+    test_type(vec!["a".to_string(), "b \"quoted\"".to_string()]).await?;
+    test_type(vec![vec![true], vec![false, true]]).await?;
+    test_type(Vec::<i64>::new()).await
+}
+
+#[tokio::test]
+async fn test_map() -> eyre::Result<()> {
+    let map = HashMap::from([("a".to_string(), 1i64), ("b".to_string(), 2)]);
+    test_type(map).await?;
+    let nested = HashMap::from([
+        ("a".to_string(), vec!["x".to_string()]),
+        ("b".to_string(), vec![]),
+    ]);
+    test_type(nested).await
+}
+
+#[tokio::test]
+async fn test_list_elements() -> eyre::Result<()> {
+    use gluesql_derive::PayloadExt;
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct Elements {
+        floats: Vec<f64>,
+        decimals: Vec<rust_decimal::Decimal>,
+        uuids: Vec<uuid::Uuid>,
+        timestamps: Vec<chrono::NaiveDateTime>,
+        instants: HashMap<String, chrono::DateTime<Utc>>,
+    }
+    let now = Utc::now();
+    let elements = Elements {
+        floats: vec![1.0, -2.5],
+        decimals: vec![
+            rust_decimal::Decimal::from_str("1.10")?,
+            rust_decimal::Decimal::from_str("12345678901234567890.123456789")?,
+        ],
+        uuids: vec![uuid::Uuid::new_v4()],
+        timestamps: vec![now.naive_utc()],
+        instants: HashMap::from([("now".to_string(), now)]),
+    };
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(Elements::get_ddl("elements")).await?;
+    let insert = ast_builder::table("elements")
+        .insert()
+        .columns(Elements::columns())
+        .values(vec![elements.try_to_gluesql_row()?])
+        .build()?;
+    glue.execute_stmt(&insert).await?;
+    let read = glue
+        .execute("SELECT * FROM elements")
+        .await?
+        .one_as::<Elements>()?;
+    assert_eq!(read, elements);
+    Ok(())
+}
+
+#[test]
+fn test_list_unsupported_elements() {
+    use gluesql_derive::Error;
+
+    assert!(matches!(
+        vec![1.5f32].try_to_gluesql(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+    assert!(matches!(
+        vec![u64::MAX].try_to_gluesql(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+    assert!(matches!(
+        HashMap::from([("nan".to_string(), f64::NAN)]).try_to_gluesql(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+    assert!(vec![vec![Some(1u64)], vec![None]].try_to_gluesql().is_ok());
+}
+
+#[tokio::test]
+async fn test_bytes() -> eyre::Result<()> {
+    test_type(bytes::Bytes::from_static(&[0, 1, 0xab, 0xff])).await
+}
+
+#[tokio::test]
+async fn test_enum() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, GlueSqlEnum)]
+    #[gluesql(rename_all = "SCREAMING_SNAKE_CASE")]
+    enum Status {
+        InProgress,
+    }
+    #[derive(Debug, PartialEq, GlueSqlEnum)]
+    #[gluesql(repr = "int")]
+    enum Priority {
+        High = 10,
+    }
+    test_type(Status::InProgress).await?;
+    test_type(Priority::High).await
+}
+
+#[tokio::test]
+async fn test_flatten() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct Address {
+        city: String,
+        zip: Option<String>,
+    }
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct User {
+        id: u64,
+        #[gluesql(flatten)]
+        address: Address,
+        name: String,
+    }
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute(User::get_ddl("user").as_str()).await?;
+    let user = User {
+        id: 1,
+        address: Address {
+            city: "Paris".to_string(),
+            zip: None,
+        },
+        name: "alice".to_string(),
+    };
+    let insert = ast_builder::table("user")
+        .insert()
+        .columns(User::columns())
+        .values(vec![user.to_gluesql_row()])
+        .build()?;
+    glue.execute_stmt(&insert).await?;
+    let select = ast_builder::table("user")
+        .select()
+        .project(User::columns())
+        .build()?;
+    match glue.execute_stmt(&select).await? {
+        Payload::Select { labels, rows } => {
+            let rows = User::from_gluesql_rows(&labels, rows)?;
+            assert_eq!(rows, vec![user]);
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_constraints() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct User {
+        #[gluesql(primary_key)]
+        id: u64,
+        #[gluesql(unique)]
+        email: String,
+        #[gluesql(default = "0")]
+        score: i64,
+    }
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute(User::get_ddl("user").as_str()).await?;
+    glue.execute("INSERT INTO user (id, email) VALUES (1, 'a@example.com')")
+        .await?;
+    let duplicate = User {
+        id: 1,
+        email: "b@example.com".to_string(),
+        score: 0,
+    };
+    let insert = ast_builder::table("user")
+        .insert()
+        .columns(User::columns())
+        .values(vec![duplicate.to_gluesql_row()])
+        .build()?;
+    assert!(glue.execute_stmt(&insert).await.is_err());
+    match glue.execute("SELECT * FROM user").await?.remove(0) {
+        Payload::Select { labels, rows } => {
+            let rows = User::from_gluesql_rows(&labels, rows)?;
+            assert_eq!(
+                rows,
+                vec![User {
+                    id: 1,
+                    email: "a@example.com".to_string(),
+                    score: 0,
+                }]
+            );
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_index() -> eyre::Result<()> {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    #[gluesql(index(name = "user_score", columns = "score"))]
+    struct User {
+        id: u64,
+        #[gluesql(index)]
+        email: String,
+        score: i64,
+    }
+    // SharedMemoryStorage does not support indexes, so only check that GlueSQL accepts the statements
+    for ddl in User::index_ddl("user") {
+        for statement in parse(ddl.as_str())? {
+            assert!(matches!(
+                translate(&statement)?,
+                Statement::CreateIndex { table_name, .. } if table_name == "user"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rename() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    #[gluesql(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct User {
+        user_id: u64,
+        #[gluesql(rename = "name")]
+        display_name: String,
+    }
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute(User::get_ddl("user").as_str()).await?;
+    let user = User {
+        user_id: 1,
+        display_name: "alice".to_string(),
+    };
+    let insert = ast_builder::table("user")
+        .insert()
+        .columns(User::columns())
+        .values(vec![user.to_gluesql_row()])
+        .build()?;
+    glue.execute_stmt(&insert).await?;
+    match glue.execute("SELECT * FROM user").await?.remove(0) {
+        Payload::Select { labels, rows } => {
+            assert_eq!(labels, vec!["USER_ID", "name"]);
+            assert_eq!(User::from_gluesql_rows(&labels, rows)?, vec![user]);
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rename_all_ddl() -> eyre::Result<()> {
+    macro_rules! rename_all_ddl {
+        ($($rule:literal),*) => {{
+            let mut ddls = Vec::new();
+            $({
+                #[allow(unused)]
+                #[derive(ReflectGlueSqlRow)]
+                #[gluesql(rename_all = $rule, index(name = "idx_created", columns = "createdAt"))]
+                struct User {
+                    user_id: i64,
+                    #[gluesql(rename = "createdAt")]
+                    created_at: i64,
+                }
+                ddls.push((User::get_ddl("user"), User::index_ddl("user")));
+            })*
+            ddls
+        }};
+    }
+    let ddls = rename_all_ddl!(
+        "lowercase",
+        "UPPERCASE",
+        "PascalCase",
+        "camelCase",
+        "snake_case",
+        "SCREAMING_SNAKE_CASE"
+    );
+    for (ddl, index_ddl) in ddls {
+        let mut glue = Glue::new(SharedMemoryStorage::new());
+        glue.execute(ddl.as_str()).await?;
+        for index in index_ddl {
+            // SharedMemoryStorage has no indexes, parsing is what can fail here
+            translate(&parse(index.as_str())?.remove(0))?;
+        }
+        glue.execute("INSERT INTO user VALUES (1, 2)").await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_newtype_conversion() -> eyre::Result<()> {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Email(String);
+    impl TryFrom<String> for Email {
+        type Error = String;
+        fn try_from(email: String) -> Result<Self, Self::Error> {
+            if email.contains('@') {
+                Ok(Email(email))
+            } else {
+                Err(format!("invalid email: {}", email))
+            }
+        }
+    }
+    impl From<Email> for String {
+        fn from(email: Email) -> Self {
+            email.0
+        }
+    }
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct User {
+        #[gluesql(try_from = "String", into = "String")]
+        email: Email,
+    }
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute(User::get_ddl("user").as_str()).await?;
+    let user = User {
+        email: Email("alice@example.com".to_string()),
+    };
+    let insert = ast_builder::table("user")
+        .insert()
+        .columns(User::columns())
+        .values(vec![user.try_to_gluesql_row()?])
+        .build()?;
+    glue.execute_stmt(&insert).await?;
+    glue.execute("INSERT INTO user VALUES ('bob')").await?;
+    match glue.execute("SELECT * FROM user").await?.remove(0) {
+        Payload::Select { labels, rows } => {
+            let mut rows = rows.into_iter();
+            let alice = User::from_gluesql_rows(&labels, rows.next().into_iter().collect())?;
+            assert_eq!(alice, vec![user]);
+            let bob = User::from_gluesql_rows(&labels, rows.collect());
+            assert!(matches!(
+                bob,
+                Err(gluesql_derive::Error::ConversionFailed {
+                    column: "email",
+                    ..
+                })
+            ));
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timestamp_encoding() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct Event {
+        id: i64,
+        at: chrono::NaiveDateTime,
+        #[gluesql(timestamp = "micros")]
+        at_micros: chrono::DateTime<Utc>,
+        #[gluesql(timestamp = "micros")]
+        seen: Option<chrono::NaiveDateTime>,
+    }
+    assert_eq!(
+        Event::column_defs(),
+        vec![
+            "id INT NOT NULL",
+            "at TIMESTAMP NOT NULL",
+            "at_micros INT NOT NULL",
+            "seen INT",
+        ]
+    );
+    let at = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+        .unwrap()
+        .and_hms_micro_opt(3, 4, 5, 6)
+        .unwrap();
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute(Event::get_ddl("event").as_str()).await?;
+    glue.execute("INSERT INTO event VALUES (1, TIMESTAMP '2024-01-02 03:04:05.000006', 1704164645000006, NULL)")
+        .await?;
+    let event = Event {
+        id: 2,
+        at,
+        at_micros: at.and_utc(),
+        seen: Some(at),
+    };
+    let insert = ast_builder::table("event")
+        .insert()
+        .columns(Event::columns())
+        .values(vec![event.to_gluesql_row()])
+        .build()?;
+    glue.execute_stmt(&insert).await?;
+    let select = ast_builder::table("event")
+        .select()
+        .filter(ast_builder::col("at").eq(at.to_gluesql()))
+        .filter(ast_builder::col("at_micros").eq(at.and_utc().to_gluesql_micros()))
+        .project(Event::columns())
+        .build()?;
+    match glue.execute_stmt(&select).await? {
+        Payload::Select { labels, rows } => {
+            let rows = Event::from_gluesql_rows(&labels, rows)?;
+            assert_eq!(
+                rows,
+                vec![
+                    Event {
+                        id: 1,
+                        at,
+                        at_micros: at.and_utc(),
+                        seen: None,
+                    },
+                    event
+                ]
+            );
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timezone() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct Meeting {
+        id: i64,
+        utc: chrono::DateTime<chrono::FixedOffset>,
+        #[gluesql(timestamp = "rfc3339")]
+        text: chrono::DateTime<chrono::FixedOffset>,
+        #[gluesql(timestamp = "offset")]
+        starts: chrono::DateTime<chrono::FixedOffset>,
+        #[gluesql(timestamp = "offset")]
+        ends: Option<chrono::DateTime<chrono::FixedOffset>>,
+        #[gluesql(timestamp = "rfc3339")]
+        local: chrono::DateTime<chrono::Local>,
+    }
+    assert_eq!(
+        Meeting::column_defs(),
+        vec![
+            "id INT NOT NULL",
+            "utc TIMESTAMP NOT NULL",
+            "text TEXT NOT NULL",
+            "starts TIMESTAMP NOT NULL",
+            "starts_offset INT NOT NULL",
+            "ends TIMESTAMP",
+            "ends_offset INT",
+            "local TEXT NOT NULL",
+        ]
+    );
+    let at = chrono::DateTime::parse_from_rfc3339("2024-01-02T03:04:05.000006+09:30")?;
+    let db = SharedMemoryStorage::new();
+    let mut glue = Glue::new(db);
+    glue.execute(Meeting::get_ddl("meeting").as_str()).await?;
+    let meeting = Meeting {
+        id: 1,
+        utc: at,
+        text: at,
+        starts: at,
+        ends: None,
+        local: at.with_timezone(&chrono::Local),
+    };
+    let insert = ast_builder::table("meeting")
+        .insert()
+        .columns(Meeting::columns())
+        .values(vec![meeting.to_gluesql_row()])
+        .build()?;
+    glue.execute_stmt(&insert).await?;
+    let select = ast_builder::table("meeting")
+        .select()
+        .filter(ast_builder::col("starts").eq(at.to_gluesql()))
+        .project(Meeting::columns())
+        .build()?;
+    match glue.execute_stmt(&select).await? {
+        Payload::Select { labels, rows } => {
+            let rows = Meeting::from_gluesql_rows(&labels, rows)?;
+            assert_eq!(rows, vec![meeting]);
+            assert_eq!(rows[0].text.offset(), at.offset());
+            assert_eq!(rows[0].starts.offset(), at.offset());
+            assert_eq!(rows[0].utc.offset().local_minus_utc(), 0);
+        }
+        _ => panic!("unexpected result"),
+    }
+    match glue
+        .execute("SELECT starts, starts_offset, text FROM meeting")
+        .await?
+        .remove(0)
+    {
+        Payload::Select { rows, .. } => {
+            assert_eq!(
+                rows[0],
+                vec![
+                    gluesql_core::prelude::Value::Timestamp(at.naive_utc()),
+                    gluesql_core::prelude::Value::I64(34200),
+                    gluesql_core::prelude::Value::Str(at.to_rfc3339()),
+                ]
+            );
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_newtype() -> eyre::Result<()> {
+    #[derive(Debug, PartialEq, GlueSqlValue)]
+    struct UserId(u64);
+    #[derive(Debug, PartialEq, GlueSqlValue)]
+    struct Email {
+        address: Option<String>,
+    }
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    #[gluesql(transparent)]
+    struct Score(i32);
+
+    assert_eq!(
+        UserId::reflect_gluesql_type_with_nullability(),
+        "UINT64 NOT NULL"
+    );
+    assert_eq!(Email::reflect_gluesql_type_with_nullability(), "TEXT NULL");
+    test_type(UserId(42)).await?;
+    test_type(Email {
+        address: Some("a@example.com".to_string()),
+    })
+    .await?;
+    test_type(Score(-7)).await
+}
+
+#[tokio::test]
+async fn test_skip_and_default() -> eyre::Result<()> {
+    use gluesql_derive::PayloadExt;
+
+    fn unnamed() -> String {
+        "anonymous".to_string()
+    }
+    #[derive(Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct User {
+        #[gluesql(primary_key)]
+        id: u64,
+        #[gluesql(default_with = "unnamed")]
+        name: String,
+        #[gluesql(default)]
+        score: i64,
+        #[gluesql(skip)]
+        cache: Vec<u8>,
+        #[gluesql(skip, default_with = "unnamed")]
+        label: String,
+    }
+
+    assert_eq!(User::columns(), vec!["id", "name", "score"]);
+    assert_eq!(
+        User::get_ddl("user"),
+        "CREATE TABLE IF NOT EXISTS user (\nid UINT64 NOT NULL PRIMARY KEY, \nname TEXT NOT NULL, \nscore INT NOT NULL);"
+    );
+    let user = User {
+        id: 1,
+        name: "alice".to_string(),
+        score: 10,
+        cache: vec![1, 2, 3],
+        label: "admin".to_string(),
+    };
+    assert_eq!(user.to_gluesql_row().len(), 3);
+
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE user (id UINT64 NOT NULL PRIMARY KEY, name TEXT NULL)")
+        .await?;
+    glue.execute("INSERT INTO user VALUES (1, 'alice'), (2, NULL)")
+        .await?;
+    let users = glue
+        .execute("SELECT * FROM user ORDER BY id")
+        .await?
+        .rows_as::<User>()?;
+    assert_eq!(
+        users,
+        vec![
+            User {
+                id: 1,
+                name: "alice".to_string(),
+                score: 0,
+                cache: vec![],
+                label: "anonymous".to_string(),
+            },
+            User {
+                id: 2,
+                name: "anonymous".to_string(),
+                score: 0,
+                cache: vec![],
+                label: "anonymous".to_string(),
+            },
+        ]
+    );
+    Ok(())
 }
@@ -0,0 +1,130 @@
+use gluesql_core::ast::DataType;
+use gluesql_core::prelude::{Glue, Payload, Value};
+use gluesql_derive::{
+    diff_table, migrate_table, Error, MigrationMode, ReflectGlueSqlRow, SchemaChange,
+};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+#[allow(unused)]
+#[derive(ReflectGlueSqlRow)]
+struct UserV1 {
+    #[gluesql(primary_key)]
+    id: u64,
+    name: String,
+    nickname: Option<String>,
+}
+
+#[allow(unused)]
+#[derive(ReflectGlueSqlRow)]
+struct UserV2 {
+    #[gluesql(primary_key)]
+    id: u64,
+    #[gluesql(renamed_from = "name")]
+    full_name: String,
+    age: Option<i64>,
+    #[gluesql(default = "'active'")]
+    status: String,
+}
+
+#[tokio::test]
+async fn test_migrate_create() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    let result = migrate_table::<_, UserV1>(&mut glue, "user", MigrationMode::VerifyOnly).await;
+    assert!(matches!(
+        result,
+        Err(Error::SchemaMismatch { table, changes })
+            if table == "user" && changes == vec![SchemaChange::CreateTable(UserV1::schema("user")?)]
+    ));
+
+    let changes = migrate_table::<_, UserV1>(&mut glue, "user", MigrationMode::Apply).await?;
+    assert_eq!(changes.len(), 1);
+    assert_eq!(diff_table::<_, UserV1>(&glue, "user").await?, vec![]);
+    assert_eq!(
+        migrate_table::<_, UserV1>(&mut glue, "user", MigrationMode::VerifyOnly).await?,
+        vec![]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_alter() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(UserV1::get_ddl("user")).await?;
+    glue.execute("INSERT INTO user VALUES (1, 'alice', NULL)")
+        .await?;
+
+    let changes = diff_table::<_, UserV2>(&glue, "user").await?;
+    let [SchemaChange::RenameColumn { from, to }, SchemaChange::DropColumn(dropped), SchemaChange::AddColumn(age), SchemaChange::AddColumn(status)] =
+        &changes[..]
+    else {
+        panic!("unexpected changes: {:?}", changes);
+    };
+    assert_eq!((from.as_str(), to.as_str()), ("name", "full_name"));
+    assert_eq!(dropped, "nickname");
+    assert_eq!((age.name.as_str(), &age.data_type), ("age", &DataType::Int));
+    assert_eq!(status.name, "status");
+    assert_eq!(
+        changes[0].to_sql("user").as_deref(),
+        Some(r#"ALTER TABLE "user" RENAME COLUMN "name" TO "full_name";"#)
+    );
+    assert_eq!(
+        changes[1].to_sql("user").as_deref(),
+        Some(r#"ALTER TABLE "user" DROP COLUMN "nickname";"#)
+    );
+
+    assert!(matches!(
+        migrate_table::<_, UserV2>(&mut glue, "user", MigrationMode::VerifyOnly).await,
+        Err(Error::SchemaMismatch { changes: found, .. }) if found == changes
+    ));
+    assert_eq!(
+        migrate_table::<_, UserV2>(&mut glue, "user", MigrationMode::Apply).await?,
+        changes
+    );
+    assert_eq!(diff_table::<_, UserV2>(&glue, "user").await?, vec![]);
+    match glue
+        .execute("SELECT full_name, age, status FROM user")
+        .await?
+        .remove(0)
+    {
+        Payload::Select { rows, .. } => assert_eq!(
+            rows,
+            vec![vec![
+                Value::Str("alice".to_string()),
+                Value::Null,
+                Value::Str("active".to_string()),
+            ]]
+        ),
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_unsupported() -> eyre::Result<()> {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct User {
+        #[gluesql(primary_key)]
+        id: u64,
+        name: Option<String>,
+        age: Option<i64>,
+    }
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(UserV1::get_ddl("user")).await?;
+
+    let changes = diff_table::<_, User>(&glue, "user").await?;
+    assert!(matches!(
+        &changes[..],
+        [SchemaChange::DropColumn(_), SchemaChange::AddColumn(_), SchemaChange::AlterColumn { stored, expected }]
+            if stored.name == "name" && !stored.nullable && expected.nullable
+    ));
+    assert_eq!(changes[2].to_sql("user"), None);
+
+    // nothing is applied when a column can't be altered
+    assert!(matches!(
+        migrate_table::<_, User>(&mut glue, "user", MigrationMode::Apply).await,
+        Err(Error::SchemaMismatch { .. })
+    ));
+    assert_eq!(diff_table::<_, User>(&glue, "user").await?, changes);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use gluesql_core::prelude::{Glue, Payload, Value};
+use gluesql_derive::{Error, Migration, Migrator, ReflectGlueSqlRow, MIGRATIONS_TABLE};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+mod common;
+use common::MockStorage;
+
+#[allow(unused)]
+#[derive(ReflectGlueSqlRow)]
+struct User {
+    #[gluesql(primary_key)]
+    id: u64,
+    name: String,
+}
+
+fn migrator() -> Migrator {
+    Migrator::new()
+        .migration(Migration::new(
+            2,
+            "add_age",
+            "ALTER TABLE user ADD COLUMN age INT NULL;",
+        ))
+        .migration(Migration::create_table::<User>(1, "user"))
+}
+
+#[tokio::test]
+async fn test_migrator_run() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    let migrations = migrator();
+    let pending = migrations.pending(&glue).await?;
+    assert_eq!(
+        pending.iter().map(|m| m.version).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(pending[0].name, "create_user");
+
+    assert_eq!(migrator().run(&mut glue).await?, vec![1, 2]);
+    glue.execute("INSERT INTO user VALUES (1, 'alice', 30)")
+        .await?;
+
+    // idempotent
+    assert!(migrator().run(&mut glue).await?.is_empty());
+    let migrator = migrator().migration(Migration::new(
+        3,
+        "drop_age",
+        "ALTER TABLE user DROP COLUMN age;",
+    ));
+    assert_eq!(migrator.run(&mut glue).await?, vec![3]);
+
+    match glue
+        .execute(format!(
+            "SELECT version, name FROM {} ORDER BY version",
+            MIGRATIONS_TABLE
+        ))
+        .await?
+        .remove(0)
+    {
+        Payload::Select { rows, .. } => assert_eq!(
+            rows,
+            vec![
+                vec![Value::I64(1), Value::Str("create_user".to_string())],
+                vec![Value::I64(2), Value::Str("add_age".to_string())],
+                vec![Value::I64(3), Value::Str("drop_age".to_string())],
+            ]
+        ),
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrator_errors() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    migrator().run(&mut glue).await?;
+
+    let changed = Migrator::new().migration(Migration::new(
+        2,
+        "add_age",
+        "ALTER TABLE user ADD COLUMN age INT;",
+    ));
+    assert!(matches!(
+        changed.pending(&glue).await,
+        Err(Error::MigrationChecksumMismatch { version: 2, .. })
+    ));
+
+    let duplicate = migrator().migration(Migration::new(1, "again", "SELECT 1;"));
+    assert!(matches!(
+        duplicate.run(&mut glue).await,
+        Err(Error::DuplicateMigration(1))
+    ));
+
+    // a failing migration is not recorded
+    let failing = migrator().migration(Migration::new(3, "broken", "DROP TABLE missing;"));
+    assert!(failing.run(&mut glue).await.is_err());
+    assert_eq!(failing.pending(&glue).await?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrator_struct_changed() -> eyre::Result<()> {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct UserV2 {
+        #[gluesql(primary_key)]
+        id: u64,
+        name: String,
+        email: Option<String>,
+    }
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    migrator().run(&mut glue).await?;
+
+    // the struct gained a field after `create_user` was applied
+    let changed = Migrator::new()
+        .migration(Migration::create_table::<UserV2>(1, "user"))
+        .migration(Migration::new(
+            2,
+            "add_age",
+            "ALTER TABLE user ADD COLUMN age INT NULL;",
+        ));
+    assert!(changed.pending(&glue).await?.is_empty());
+    assert!(changed.run(&mut glue).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrator_transaction() -> eyre::Result<()> {
+    use gluesql_core::store::Store;
+
+    let mut glue = Glue::new(MockStorage::default());
+    let migrator = Migrator::new()
+        .migration(Migration::create_table::<User>(1, "user"))
+        .migration(Migration::new(
+            2,
+            "broken",
+            "CREATE TABLE audit (id INT); DROP TABLE missing;",
+        ));
+
+    // the failing migration is rolled back, the one before it stays applied
+    let error = migrator.run(&mut glue).await.unwrap_err();
+    assert!(error.to_string().contains("missing"), "{}", error);
+    assert!(glue.storage.fetch_schema("audit").await?.is_none());
+    assert!(glue.storage.fetch_schema("user").await?.is_some());
+    let pending = migrator.pending(&glue).await?;
+    assert_eq!(
+        pending.iter().map(|m| m.version).collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    // a failing rollback doesn't hide the error of the migration
+    glue.storage.fail_rollback = true;
+    let error = migrator.run(&mut glue).await.unwrap_err();
+    assert!(error.to_string().contains("missing"), "{}", error);
+    Ok(())
+}
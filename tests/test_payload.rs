@@ -0,0 +1,135 @@
+use gluesql_core::prelude::{Glue, Payload};
+use gluesql_derive::{Error, FromGlueSqlRow, PayloadExt};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+#[derive(Debug, PartialEq, FromGlueSqlRow)]
+struct Item {
+    id: i64,
+    name: String,
+}
+
+async fn glue() -> eyre::Result<Glue<SharedMemoryStorage>> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE item (id INT, name TEXT)")
+        .await?;
+    let inserted = glue
+        .execute("INSERT INTO item VALUES (1, 'a'), (2, 'b')")
+        .await?
+        .affected_rows()?;
+    assert_eq!(inserted, 2);
+    Ok(glue)
+}
+
+#[tokio::test]
+async fn test_payload_rows() -> eyre::Result<()> {
+    let mut glue = glue().await?;
+    let items = glue
+        .execute("SELECT * FROM item ORDER BY id")
+        .await?
+        .rows_as::<Item>()?;
+    assert_eq!(items.len(), 2);
+
+    let item = glue
+        .execute("SELECT * FROM item WHERE name = 'b'")
+        .await?
+        .one_as::<Item>()?;
+    assert_eq!(
+        item,
+        Item {
+            id: 2,
+            name: "b".to_string()
+        }
+    );
+    assert!(matches!(
+        glue.execute("SELECT * FROM item").await?.one_as::<Item>(),
+        Err(Error::UnexpectedRowCount { found: 2, .. })
+    ));
+    assert!(matches!(
+        glue.execute("SELECT * FROM item WHERE id > 5")
+            .await?
+            .one_as::<Item>(),
+        Err(Error::UnexpectedRowCount { found: 0, .. })
+    ));
+
+    assert_eq!(
+        glue.execute("SELECT * FROM item WHERE id > 5")
+            .await?
+            .optional_as::<Item>()?,
+        None
+    );
+    assert!(matches!(
+        glue.execute("SELECT * FROM item")
+            .await?
+            .optional_as::<Item>(),
+        Err(Error::UnexpectedRowCount { found: 2, .. })
+    ));
+
+    let count = glue
+        .execute("SELECT COUNT(*) FROM item")
+        .await?
+        .scalar::<i64>()?;
+    assert_eq!(count, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_payload_errors() -> eyre::Result<()> {
+    let mut glue = glue().await?;
+    assert!(matches!(
+        glue.execute("DELETE FROM item WHERE id = 1")
+            .await?
+            .rows_as::<Item>(),
+        Err(Error::UnexpectedPayload("Select", payload)) if *payload == Payload::Delete(1)
+    ));
+    assert!(matches!(
+        glue.execute("SELECT * FROM item").await?.affected_rows(),
+        Err(Error::UnexpectedPayload(_, _))
+    ));
+    assert!(matches!(
+        glue.execute("SELECT 1; SELECT 2;").await?.scalar::<i64>(),
+        Err(Error::UnexpectedPayloadCount(2))
+    ));
+    assert_eq!(
+        glue.execute("UPDATE item SET name = 'c'")
+            .await?
+            .affected_rows()?,
+        1
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_payload_select_map() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE item").await?;
+    glue.execute(r#"INSERT INTO item VALUES ('{"id": 1, "name": "a"}')"#)
+        .await?;
+    let payload = glue.execute("SELECT * FROM item").await?.remove(0);
+    assert!(matches!(payload, Payload::SelectMap(_)));
+    assert_eq!(
+        payload.one_as::<Item>()?,
+        Item {
+            id: 1,
+            name: "a".to_string()
+        }
+    );
+
+    // no column order to decode by position
+    assert!(matches!(
+        glue.execute("SELECT * FROM item").await?.scalar::<i64>(),
+        Err(Error::UnorderedColumns(_))
+    ));
+    assert!(matches!(
+        glue.execute("SELECT * FROM item")
+            .await?
+            .rows_as::<(i64, String)>(),
+        Err(Error::UnorderedColumns(_))
+    ));
+    assert!(matches!(
+        glue.execute("SELECT * FROM item")
+            .await?
+            .optional_as::<(i64,)>(),
+        Err(Error::UnorderedColumns(_))
+    ));
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use gluesql_derive::ReflectGlueSqlRow;
+use gluesql_derive::{GlueSqlTable, ReflectGlueSqlRow};
 
 #[test]
 fn test_reflectgluesql_field_struct() {
@@ -13,3 +13,166 @@ fn test_reflectgluesql_field_struct() {
     println!("{}", Foo::get_ddl("foo"));
     assert_eq!(Foo::columns(), vec!["a", "b", "c", "d"]);
 }
+
+#[test]
+fn test_reflectgluesql_flatten() {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct Audit {
+        created_at: i64,
+        updated_at: Option<i64>,
+    }
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct Foo {
+        id: i64,
+        #[gluesql(flatten)]
+        audit: Audit,
+        name: String,
+    }
+    assert_eq!(
+        Foo::columns(),
+        vec!["id", "created_at", "updated_at", "name"]
+    );
+    assert_eq!(
+        Foo::get_ddl("foo"),
+        "CREATE TABLE IF NOT EXISTS foo (\nid INT NOT NULL, \ncreated_at INT NOT NULL, \nupdated_at INT NULL, \nname TEXT NOT NULL);"
+    );
+}
+
+#[test]
+fn test_reflectgluesql_constraints() {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct Foo {
+        #[gluesql(primary_key)]
+        id: i64,
+        #[gluesql(unique)]
+        email: String,
+        #[gluesql(default = "'active'")]
+        status: String,
+    }
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct Bar {
+        #[gluesql(flatten)]
+        foo: Foo,
+    }
+    assert_eq!(Foo::primary_key(), Some("id"));
+    assert_eq!(Bar::primary_key(), Some("id"));
+    assert_eq!(
+        Foo::get_ddl("foo"),
+        "CREATE TABLE IF NOT EXISTS foo (\nid INT NOT NULL PRIMARY KEY, \nemail TEXT NOT NULL UNIQUE, \nstatus TEXT NOT NULL DEFAULT 'active');"
+    );
+}
+
+#[test]
+fn test_reflectgluesql_manual_schema() {
+    use gluesql_derive::Error;
+
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    struct Derived {
+        #[gluesql(primary_key)]
+        id: i64,
+        #[gluesql(unique)]
+        email: Option<String>,
+        #[gluesql(default = "'active'")]
+        status: String,
+    }
+    /// implements only the required methods
+    struct Manual;
+    impl ReflectGlueSqlRow for Manual {
+        fn column_defs() -> Vec<String> {
+            vec![
+                "id INT NOT NULL PRIMARY KEY".to_string(),
+                "email TEXT NULL UNIQUE".to_string(),
+                "status TEXT NOT NULL DEFAULT 'active'".to_string(),
+            ]
+        }
+        fn columns() -> Vec<&'static str> {
+            vec!["id", "email", "status"]
+        }
+    }
+    assert_eq!(Manual::column_defs(), Derived::column_defs());
+    assert_eq!(
+        Manual::schema_column_defs().unwrap(),
+        Derived::schema_column_defs().unwrap()
+    );
+
+    struct Broken;
+    impl ReflectGlueSqlRow for Broken {
+        fn column_defs() -> Vec<String> {
+            vec!["id INT); DROP TABLE users; (x INT".to_string()]
+        }
+        fn columns() -> Vec<&'static str> {
+            vec!["id"]
+        }
+    }
+    assert!(matches!(
+        Broken::schema_column_defs(),
+        Err(Error::InvalidColumnDefs(_))
+    ));
+}
+
+#[test]
+fn test_reflectgluesql_index() {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    #[gluesql(index(name = "foo_score", columns = "score"))]
+    struct Foo {
+        id: i64,
+        #[gluesql(index)]
+        name: String,
+        score: i64,
+    }
+    assert_eq!(
+        Foo::index_ddl("foo"),
+        vec![
+            "CREATE INDEX idx_foo_name ON foo (name);",
+            "CREATE INDEX foo_score ON foo (score);",
+        ]
+    );
+}
+
+#[test]
+fn test_reflectgluesql_table_name() {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    #[gluesql(table = "users")]
+    struct User {
+        id: i64,
+    }
+    assert_eq!(User::TABLE_NAME, "users");
+    assert_eq!(User::create_table_ddl(), User::get_ddl("users"));
+}
+
+#[test]
+fn test_reflectgluesql_rename() {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    #[gluesql(rename_all = "camelCase")]
+    struct Foo {
+        #[gluesql(primary_key)]
+        user_id: i64,
+        #[gluesql(rename = "full_name")]
+        display_name: String,
+    }
+    assert_eq!(Foo::columns(), vec!["userId", "full_name"]);
+    assert_eq!(Foo::primary_key(), Some("userId"));
+    assert_eq!(
+        Foo::get_ddl("foo"),
+        "CREATE TABLE IF NOT EXISTS foo (\nuserId INT NOT NULL PRIMARY KEY, \nfull_name TEXT NOT NULL);"
+    );
+}
+
+#[test]
+fn test_reflectgluesql_rename_pascal_case() {
+    #[allow(unused)]
+    #[derive(ReflectGlueSqlRow)]
+    #[gluesql(rename_all = "PascalCase")]
+    struct Foo {
+        user_id: i64,
+    }
+    assert_eq!(Foo::columns(), vec!["UserId"]);
+}
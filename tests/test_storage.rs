@@ -0,0 +1,180 @@
+use gluesql_core::prelude::{Glue, Payload, Value};
+use gluesql_derive::{
+    insert_rows, scan_rows, Error, FromGlueSqlRow, ReflectGlueSqlRow, ToGlueSqlRow,
+    ToGlueSqlValueRow,
+};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+#[derive(
+    Debug, Clone, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+)]
+struct User {
+    #[gluesql(primary_key)]
+    id: u64,
+    name: String,
+    score: Option<i64>,
+}
+
+fn users(n: u64) -> Vec<User> {
+    (0..n)
+        .map(|id| User {
+            id,
+            name: format!("user{}", id),
+            score: (id % 2 == 0).then_some(id as i64),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_storage_roundtrip() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(User::get_ddl("user")).await?;
+    let rows = users(10);
+    assert_eq!(insert_rows(&mut glue.storage, "user", &rows).await?, 10);
+
+    let mut scanned = scan_rows::<_, User>(&glue.storage, "user").await?;
+    scanned.sort_by_key(|u| u.id);
+    assert_eq!(scanned, rows);
+
+    // rows written to storage are visible to SQL
+    match glue
+        .execute("SELECT id FROM user WHERE name = 'user3'")
+        .await?
+        .remove(0)
+    {
+        Payload::Select { rows, .. } => {
+            assert_eq!(rows, vec![vec![Value::U64(3)]]);
+        }
+        _ => panic!("unexpected result"),
+    }
+
+    // same primary key replaces the row
+    let updated = User {
+        id: 3,
+        name: "alice".to_string(),
+        score: None,
+    };
+    insert_rows(&mut glue.storage, "user", std::slice::from_ref(&updated)).await?;
+    let scanned = scan_rows::<_, User>(&glue.storage, "user").await?;
+    assert_eq!(scanned.len(), 10);
+    assert!(scanned.contains(&updated));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_storage_schema() -> eyre::Result<()> {
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    struct Partial {
+        id: i32,
+        name: String,
+    }
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE partial (name TEXT, id INT, status TEXT DEFAULT 'new')")
+        .await?;
+    let row = Partial {
+        id: 1,
+        name: "a".to_string(),
+    };
+    insert_rows(&mut glue.storage, "partial", &[row]).await?;
+    match glue.execute("SELECT * FROM partial").await?.remove(0) {
+        Payload::Select { rows, .. } => {
+            assert_eq!(
+                rows,
+                vec![vec![
+                    Value::Str("a".to_string()),
+                    Value::I64(1),
+                    Value::Str("new".to_string()),
+                ]]
+            );
+        }
+        _ => panic!("unexpected result"),
+    }
+    assert_eq!(
+        scan_rows::<_, Partial>(&glue.storage, "partial").await?,
+        vec![Partial {
+            id: 1,
+            name: "a".to_string(),
+        }]
+    );
+
+    assert!(matches!(
+        scan_rows::<_, Partial>(&glue.storage, "missing").await,
+        Err(Error::TableNotFound(table)) if table == "missing"
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_storage_schemaless() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute("CREATE TABLE user").await?;
+    let rows = users(2);
+    insert_rows(&mut glue.storage, "user", &rows).await?;
+    assert_eq!(scan_rows::<_, User>(&glue.storage, "user").await?, rows);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_storage_insert_schema() -> eyre::Result<()> {
+    use gluesql_core::store::{Store, StoreMut};
+    use gluesql_derive::GlueSqlTable;
+
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    struct Audit {
+        #[gluesql(timestamp = "micros")]
+        created_at: chrono::NaiveDateTime,
+        #[gluesql(timestamp = "offset")]
+        updated_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    }
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    #[gluesql(table = "account")]
+    struct Account {
+        #[gluesql(primary_key)]
+        id: u64,
+        #[gluesql(unique)]
+        email: String,
+        #[gluesql(default = "'active'")]
+        status: String,
+        balance: Option<rust_decimal::Decimal>,
+        tags: Vec<String>,
+        #[gluesql(flatten)]
+        audit: Audit,
+    }
+
+    // the same schema as running the DDL
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(Account::create_table_ddl()).await?;
+    assert_eq!(
+        glue.storage.fetch_schema("account").await?,
+        Some(Account::table_schema()?)
+    );
+
+    let mut storage = SharedMemoryStorage::new();
+    storage.insert_schema(&Account::table_schema()?).await?;
+    let created_at = chrono::DateTime::from_timestamp_micros(1_000_001)
+        .unwrap()
+        .naive_utc();
+    let account = Account {
+        id: 1,
+        email: "a@example.com".to_string(),
+        status: "active".to_string(),
+        balance: None,
+        tags: vec!["x".to_string()],
+        audit: Audit {
+            created_at,
+            updated_at: None,
+        },
+    };
+    insert_rows(&mut storage, "account", std::slice::from_ref(&account)).await?;
+    assert_eq!(
+        scan_rows::<_, Account>(&storage, "account").await?,
+        vec![account]
+    );
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use gluesql_core::ast_builder::{col, num};
+use gluesql_core::prelude::Glue;
+use gluesql_core::store::Store;
+use gluesql_derive::{Error, FromGlueSqlRow, ReflectGlueSqlRow, Table, ToGlueSqlRow};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+mod common;
+use common::MockStorage;
+
+#[derive(Debug, Clone, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+struct User {
+    #[gluesql(primary_key)]
+    id: u64,
+    name: String,
+    age: i64,
+}
+
+fn user(id: u64, name: &str, age: i64) -> User {
+    User {
+        id,
+        name: name.to_string(),
+        age,
+    }
+}
+
+#[tokio::test]
+async fn test_table_crud() -> eyre::Result<()> {
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    let mut users = Table::<_, User>::new(&mut glue, "users");
+    users.create().await?;
+    assert_eq!(users.insert(&user(1, "alice", 30)).await?, 1);
+    assert_eq!(
+        users
+            .insert_many(&[user(2, "bob", 20), user(3, "carol", 40)])
+            .await?,
+        2
+    );
+    assert_eq!(users.insert_many(&[]).await?, 0);
+    assert_eq!(users.count().await?, 3);
+
+    let adults = users.select_where(col("age").gte(num(30))).await?;
+    assert_eq!(adults, vec![user(1, "alice", 30), user(3, "carol", 40)]);
+
+    assert_eq!(users.update_by_key(&user(2, "bobby", 21)).await?, 1);
+    assert_eq!(users.delete_by_key(&3u64).await?, 1);
+    assert_eq!(
+        users.select_all().await?,
+        vec![user(1, "alice", 30), user(2, "bobby", 21)]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_missing_primary_key() -> eyre::Result<()> {
+    #[derive(ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    struct Log {
+        message: String,
+    }
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    let mut logs = Table::<_, Log>::new(&mut glue, "logs");
+    logs.create().await?;
+    let err = logs.delete_by_key(&"hello").await.unwrap_err();
+    assert!(matches!(err, Error::MissingPrimaryKey(_)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_create_twice() -> eyre::Result<()> {
+    #[derive(ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow)]
+    #[gluesql(index(name = "idx_name", columns = "name"))]
+    struct Tag {
+        id: i64,
+        name: String,
+    }
+    let mut glue = Glue::new(MockStorage::default());
+    let mut tags = Table::<_, Tag>::new(&mut glue, "tags");
+    tags.create().await?;
+    tags.create().await?;
+    let schema = glue.storage.fetch_schema("tags").await?.unwrap();
+    let indexes = schema
+        .indexes
+        .iter()
+        .map(|i| i.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(indexes, vec!["idx_name"]);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use gluesql_derive::ToGlueSqlRow;
+use gluesql_derive::{ToGlueSqlRow, ToGlueSqlValueRow};
 use std::str::FromStr;
 
 #[test]
@@ -21,3 +21,139 @@ fn test_togluesql_field_struct() {
     let row = data.to_gluesql_row();
     println!("{:?}", row);
 }
+
+#[test]
+fn test_togluesql_values() {
+    use gluesql_core::data::Value;
+
+    #[derive(ToGlueSqlRow, ToGlueSqlValueRow)]
+    struct Audit {
+        #[gluesql(timestamp = "micros")]
+        created_at: chrono::NaiveDateTime,
+    }
+    #[derive(ToGlueSqlRow, ToGlueSqlValueRow)]
+    struct Foo {
+        a: rust_decimal::Decimal,
+        b: uuid::Uuid,
+        c: std::net::IpAddr,
+        #[gluesql(flatten)]
+        audit: Audit,
+        #[gluesql(timestamp = "offset")]
+        d: Option<chrono::DateTime<chrono::FixedOffset>>,
+        #[gluesql(into = "i64")]
+        e: i32,
+    }
+    let created_at = chrono::DateTime::from_timestamp_micros(1_000_001)
+        .unwrap()
+        .naive_utc();
+    let data = Foo {
+        a: rust_decimal::Decimal::from_str("1.23").unwrap(),
+        b: uuid::Uuid::from_u128(42),
+        c: "127.0.0.1".parse().unwrap(),
+        audit: Audit { created_at },
+        d: None,
+        e: 7,
+    };
+    assert_eq!(
+        data.to_gluesql_values(),
+        vec![
+            Value::Decimal(rust_decimal::Decimal::from_str("1.23").unwrap()),
+            Value::Uuid(42),
+            Value::Inet("127.0.0.1".parse().unwrap()),
+            Value::I64(1_000_001),
+            Value::Null,
+            Value::Null,
+            Value::I64(7),
+        ]
+    );
+    assert_eq!(data.to_gluesql_values().len(), data.to_gluesql_row().len());
+}
+
+#[test]
+fn test_togluesql_expr_only() {
+    use gluesql_core::ast_builder::{text, ExprNode};
+    use gluesql_derive::ToGlueSql;
+
+    /// only written through `ExprNode`, without a `ToGlueSqlValue` implementation
+    struct Tag(&'static str);
+    impl ToGlueSql for Tag {
+        fn to_gluesql(&self) -> ExprNode<'static> {
+            text(self.0)
+        }
+    }
+    #[derive(ToGlueSqlRow)]
+    struct Post {
+        id: i64,
+        tag: Tag,
+    }
+    struct Manual;
+    impl ToGlueSqlRow for Manual {
+        fn to_gluesql_row(&self) -> Vec<ExprNode<'static>> {
+            vec![text("manual")]
+        }
+    }
+    let post = Post {
+        id: 1,
+        tag: Tag("rust"),
+    };
+    assert_eq!(post.to_gluesql_row().len(), 2);
+    assert_eq!(Manual.try_to_gluesql_row().unwrap().len(), 1);
+}
+
+#[test]
+fn test_togluesql_duration_overflow() {
+    use gluesql_derive::{Error, ToGlueSql};
+
+    #[derive(ToGlueSqlRow, ToGlueSqlValueRow)]
+    struct Timeout {
+        std: std::time::Duration,
+        chrono: Option<chrono::Duration>,
+    }
+    let max = std::time::Duration::from_micros(i64::MAX as u64);
+    assert!(max.try_to_gluesql().is_ok());
+    assert!(matches!(
+        (max + std::time::Duration::from_micros(1)).try_to_gluesql(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+    assert!(chrono::Duration::microseconds(i64::MAX)
+        .try_to_gluesql()
+        .is_ok());
+    assert!(matches!(
+        chrono::Duration::MAX.try_to_gluesql(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+
+    let timeout = Timeout {
+        std: std::time::Duration::MAX,
+        chrono: None,
+    };
+    assert!(matches!(
+        timeout.try_to_gluesql_row(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+    let timeout = Timeout {
+        std: max,
+        chrono: Some(chrono::Duration::MIN),
+    };
+    assert!(timeout.try_to_gluesql_row().is_err());
+    assert!(matches!(
+        timeout.try_to_gluesql_values(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+}
+
+#[test]
+fn test_togluesql_value_duration_overflow() {
+    use gluesql_derive::{Error, ToGlueSqlValue};
+
+    let max = std::time::Duration::from_micros(i64::MAX as u64);
+    assert!(max.try_to_gluesql_value().is_ok());
+    assert!(matches!(
+        vec![max, max + std::time::Duration::from_micros(1)].try_to_gluesql_value(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+    assert!(matches!(
+        Some(chrono::Duration::MAX).try_to_gluesql_value(),
+        Err(Error::UnsupportedValue { .. })
+    ));
+}
@@ -0,0 +1,57 @@
+use gluesql_core::prelude::{Glue, Payload};
+use gluesql_derive::{FromGlueSqlRow, ReflectGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow};
+use gluesql_shared_memory_storage::SharedMemoryStorage;
+
+#[tokio::test]
+async fn test_tuple_struct() -> eyre::Result<()> {
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    struct Pair(i64, #[gluesql(rename = "label")] String, Option<bool>);
+
+    assert_eq!(Pair::columns(), vec!["c0", "label", "c2"]);
+    assert_eq!(
+        Pair::get_ddl("pair"),
+        "CREATE TABLE IF NOT EXISTS pair (\nc0 INT NOT NULL, \nlabel TEXT NOT NULL, \nc2 BOOLEAN NULL);"
+    );
+
+    let mut glue = Glue::new(SharedMemoryStorage::new());
+    glue.execute(Pair::get_ddl("pair")).await?;
+    let pair = Pair(1, "one".to_string(), None);
+    gluesql_derive::insert_rows(&mut glue.storage, "pair", std::slice::from_ref(&pair)).await?;
+    match glue
+        .execute("SELECT label, c2, c0 FROM pair")
+        .await?
+        .remove(0)
+    {
+        Payload::Select { labels, rows } => {
+            assert_eq!(Pair::from_gluesql_rows(&labels, rows)?, vec![pair]);
+        }
+        _ => panic!("unexpected result"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_tuple_struct_flatten() {
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    struct Audit(#[gluesql(rename = "created_at")] i64);
+    #[derive(
+        Debug, PartialEq, ReflectGlueSqlRow, FromGlueSqlRow, ToGlueSqlRow, ToGlueSqlValueRow,
+    )]
+    struct User(#[gluesql(primary_key)] u64, #[gluesql(flatten)] Audit);
+
+    assert_eq!(User::columns(), vec!["c0", "created_at"]);
+    assert_eq!(User::primary_key(), Some("c0"));
+    let user = User(1, Audit(2));
+    let labels = User::columns()
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        User::from_gluesql_row(&labels, user.to_gluesql_values()).unwrap(),
+        user
+    );
+}